@@ -10,8 +10,9 @@ use rayon::prelude::*;
 use rug::Float;
 
 use fractran::parse::{load_lines, parse_program};
-use fractran::program::{BigInt, State};
-use fractran::shift_sim::{ShiftSim, SimStatus, find_shift_rules};
+use fractran::program::{BigInt, Int, State};
+use fractran::shift_sim::{ShiftSim, DEFAULT_LOOP_TELEPORT_CAP};
+use fractran::simulator::{SimStatus, Simulator};
 
 struct TaskResult {
     program_str: String,
@@ -24,22 +25,27 @@ struct TaskResult {
 }
 
 // Helper function to run the simulation and collect results
-fn parse_and_sim(program_str: &str, transcript_steps: usize, sim_steps: usize) -> TaskResult {
+fn parse_and_sim(
+    program_str: &str,
+    transcript_steps: usize,
+    sim_steps: usize,
+    loop_teleport_cap: Int,
+) -> TaskResult {
     let start_time = Instant::now();
     let prog = parse_program(program_str);
     let start_state = State::start(&prog);
 
-    let shift_rules = find_shift_rules(&prog, start_state.clone(), transcript_steps);
-    let mut sim = ShiftSim::new(prog, shift_rules);
-    let config = sim.run(start_state, sim_steps);
+    let mut sim = ShiftSim::build(prog, start_state.clone(), transcript_steps as Int)
+        .with_loop_teleport_cap(loop_teleport_cap);
+    let config = sim.run(start_state, sim_steps as Int);
 
     TaskResult {
         program_str: program_str.to_string(),
         duration: start_time.elapsed(),
         config,
-        sim_status: sim.status,
-        sim_steps: sim.sim_steps,
-        base_steps: sim.base_steps,
+        sim_status: sim.status(),
+        sim_steps: sim.sim_steps() as usize,
+        base_steps: sim.base_steps(),
     }
 }
 
@@ -54,6 +60,12 @@ struct Args {
     transcript_steps: usize,
     sim_steps: usize,
     outfile: String,
+
+    /// Cap on how far the loop detector's checkpoint is allowed to teleport ahead between
+    /// refreshes, bounding per-program work from the cycle-detection pass (see
+    /// ShiftSim::with_loop_teleport_cap). Lower this when sweeping many programs at once.
+    #[arg(long, default_value_t = DEFAULT_LOOP_TELEPORT_CAP)]
+    loop_teleport_cap: Int,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -80,6 +92,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 program_str,
                 args.transcript_steps,
                 args.sim_steps,
+                args.loop_teleport_cap,
             ))
         })
         .collect(); // Collect results back into a Vec on the main thread
@@ -5,8 +5,9 @@ use std::time::Instant;
 use clap::Parser;
 
 use fractran::parse::load_program;
-use fractran::program::State;
-use fractran::shift_sim::{find_shift_rules, ShiftSim, SimStatus};
+use fractran::program::{Int, State};
+use fractran::shift_sim::{ShiftSim, DEFAULT_LOOP_TELEPORT_CAP};
+use fractran::simulator::{SimStatus, Simulator};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -16,10 +17,15 @@ struct Args {
     filename_record: String,
 
     #[arg(default_value_t = 1_000)]
-    transcript_steps: usize,
+    transcript_steps: Int,
 
     #[arg(default_value_t = 1_000_000)]
-    print_steps: usize,
+    print_steps: Int,
+
+    /// Cap on how far the loop detector's checkpoint is allowed to teleport ahead between
+    /// refreshes (see ShiftSim::with_loop_teleport_cap).
+    #[arg(default_value_t = DEFAULT_LOOP_TELEPORT_CAP)]
+    loop_teleport_cap: Int,
 }
 
 fn main() {
@@ -29,19 +35,17 @@ fn main() {
     let prog = load_program(&args.filename_record).expect("Couldn't load program from file");
     let mut state = State::start(&prog);
 
-    let shift_rules = find_shift_rules(&prog, state.clone(), args.transcript_steps);
-    println!("Discovered {} shift rules", shift_rules.len());
-
-    let mut sim = ShiftSim::new(prog, shift_rules);
-    while sim.status == SimStatus::Running {
+    let mut sim = ShiftSim::build(prog, state.clone(), args.transcript_steps)
+        .with_loop_teleport_cap(args.loop_teleport_cap);
+    while sim.status() == SimStatus::Running {
         state = sim.run(state, args.print_steps);
         println!(
             "Sim Step: {}  {:?}  ({:.2}s)",
-            sim.sim_steps,
+            sim.sim_steps(),
             state,
             start.elapsed().as_secs_f64()
         );
     }
 
-    println!("Status: {:?}  sim step: {}", sim.status, sim.sim_steps);
+    println!("Status: {:?}  sim step: {}", sim.status(), sim.sim_steps());
 }
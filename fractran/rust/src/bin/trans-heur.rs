@@ -31,8 +31,8 @@ use rayon::prelude::*;
 
 use fractran::parse::{load_lines, parse_program};
 use fractran::program::{Int, State};
-use fractran::tandem_repeat::{as_rep_blocks, find_repeat_info, rep_stats, RepBlockStats};
-use fractran::transcript::{strip_reps, transcript};
+use fractran::tandem_repeat::{find_repeat_info, find_repeat_info_streaming, rep_stats, RepBlockStats, DEFAULT_MAX_WINDOW};
+use fractran::transcript::{strip_reps, transcript_iter};
 
 struct TaskResult {
     program_str: String,
@@ -49,13 +49,12 @@ fn process_task(program_str: &str, num_steps: Int) -> TaskResult {
     let prog = parse_program(program_str);
     let state = State::start(&prog);
 
-    // Load sequence of transitions ("transcript")
-    let trans_vec = transcript(&prog, state, num_steps);
-
-    // Find base-level (L0) repeats in transcript
-    let l0_rep_info = find_repeat_info(&trans_vec);
-    let l0_stats = rep_stats(&l0_rep_info, trans_vec.len());
-    let l0_rep_blocks = as_rep_blocks(&trans_vec, l0_rep_info);
+    // Stream the transcript straight into L0 repeat detection, so we never hold the full
+    // (potentially huge) transition history in memory, nor re-scan it to build rep blocks.
+    let (l0_blocks, l0_stats_handle) =
+        find_repeat_info_streaming(transcript_iter(prog, state, num_steps as usize), DEFAULT_MAX_WINDOW);
+    let l0_rep_blocks = l0_blocks.collect_vec();
+    let l0_stats = l0_stats_handle.borrow().clone();
 
     // Find next level (L1) repeats in l0_rep_blocks
     let l0_block_pattern = strip_reps(l0_rep_blocks);
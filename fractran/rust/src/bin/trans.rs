@@ -1,7 +1,5 @@
 // Simulate program printing out transcript and experimenting with transcript compression.
 
-use std::collections::HashSet;
-
 use clap::Parser;
 
 use fractran::diff_rule::DiffRule;
@@ -9,6 +7,7 @@ use fractran::parse::load_program;
 use fractran::program::{Int, State};
 use fractran::tandem_repeat::{find_rep_blocks, RepBlock, ToStringVec};
 use fractran::transcript::{strip_reps, transcript, Trans};
+use fractran::union_find::UnionFind;
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -44,21 +43,65 @@ fn main() {
     );
     println!();
 
-    // Print rules
-    let seqs: HashSet<&Vec<Trans>> = rep_blocks
+    // Collect each physically distinct repeated block, in first-occurrence order, and derive
+    // its DiffRule.
+    let mut distinct_blocks: Vec<Vec<Trans>> = Vec::new();
+    for r in rep_blocks.iter().filter(|r| r.rep != 1) {
+        if !distinct_blocks.contains(&r.block) {
+            distinct_blocks.push(r.block.clone());
+        }
+    }
+    let rules: Vec<DiffRule> = distinct_blocks
         .iter()
-        .filter(|r| r.rep != 1)
-        .map(|r| &r.block)
+        .map(|seq| DiffRule::from_trans_vec(&prog, seq).unwrap())
         .collect();
-    for seq in seqs.iter() {
-        println!("Seq: {}", Trans::to_string_vec(seq));
-        let rule = DiffRule::from_trans_vec(&prog, seq).unwrap();
-        println!("Rule: {}", rule);
+
+    // Union any two distinct blocks whose DiffRules have an equal (or scalar-multiple) delta:
+    // they're the same underlying rule, just applied a different number of times.
+    let mut uf = UnionFind::new(rules.len());
+    for i in 0..rules.len() {
+        for j in (i + 1)..rules.len() {
+            if rules[i].delta == rules[j].delta || rules[i].delta_is_scalar_multiple(&rules[j]) {
+                uf.union(i, j);
+            }
+        }
+    }
+    let roots: Vec<usize> = (0..rules.len()).map(|i| uf.find(i)).collect();
+
+    let mut class_members: Vec<Vec<usize>> = vec![Vec::new(); rules.len()];
+    for (i, &root) in roots.iter().enumerate() {
+        class_members[root].push(i);
+    }
+    println!(
+        "{} physically distinct repeated block(s) collapse into {} canonical rule(s):",
+        rules.len(),
+        class_members.iter().filter(|m| !m.is_empty()).count()
+    );
+    for (root, members) in class_members.iter().enumerate() {
+        if members.is_empty() {
+            continue;
+        }
+        println!("Seq: {}", Trans::to_string_vec(&distinct_blocks[root]));
+        println!("Rule: {}  ({} block(s) collapsed here)", rules[root], members.len());
     }
     println!();
 
-    // Find higher level repeated patterns in rep_blocks
-    let block_pattern = strip_reps(rep_blocks);
+    // Relabel each repeated rep-block onto its canonical class representative's content, so the
+    // higher-level pass below treats equivalent rules as one symbol instead of
+    // accidentally-distinct ones.
+    let canonical_rep_blocks: Vec<RepBlock<Trans>> = rep_blocks
+        .into_iter()
+        .map(|r| {
+            if r.rep == 1 {
+                return r;
+            }
+            let idx = distinct_blocks.iter().position(|b| *b == r.block).unwrap();
+            RepBlock { block: distinct_blocks[roots[idx]].clone(), rep: r.rep }
+        })
+        .collect();
+
+    // Find higher level repeated patterns in canonicalized rep_blocks
+    let block_pattern = strip_reps(canonical_rep_blocks);
     let meta_rep_blocks = find_rep_blocks(&block_pattern);
     println!(
         "Compressed Transcript: {}",
@@ -14,10 +14,19 @@ pub enum MagnitudeCon {
     Min(Int),
 }
 
+// A modular (residue) constraint: value % modulus == residue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModularCon {
+    pub modulus: Int,
+    pub residue: Int,
+}
+
 // General constraint. Includes
 pub struct Constraint {
     pub mag: MagnitudeCon,
-    // TODO: Add Modularity constraints like x % 2 == 1, allow multiple
+    // Modular preconditions, e.g. x % 2 == 1. Kept merged down to at most one entry by
+    // `add_modular`, so `mods` never holds two constraints on the same register at once.
+    pub mods: Vec<ModularCon>,
     // TODO: Allow cross register constraints like x > y + 1? That will be hard ...
 }
 
@@ -42,7 +51,165 @@ impl MagnitudeCon {
         } else {
             ConstraintResult::Failure(Constraint {
                 mag: MagnitudeCon::Equals(val),
+                mods: Vec::new(),
+            })
+        }
+    }
+}
+
+impl ModularCon {
+    pub fn eval(&self, val: Int) -> ConstraintResult {
+        if val.rem_euclid(self.modulus) == self.residue {
+            ConstraintResult::Success
+        } else {
+            ConstraintResult::Failure(Constraint {
+                mag: MagnitudeCon::Unconstrained,
+                mods: vec![ModularCon {
+                    modulus: self.modulus,
+                    residue: val.rem_euclid(self.modulus),
+                }],
             })
         }
     }
 }
+
+// Extended Euclidean algorithm: returns (g, x, y) with g == gcd(a, b) and a*x + b*y == g.
+fn extended_gcd(a: Int, b: Int) -> (Int, Int, Int) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x1, y1) = extended_gcd(b, a.rem_euclid(b));
+        (g, y1, x1 - (a / b) * y1)
+    }
+}
+
+// Combine two residue constraints on the same register via CRT: `(r1 mod m1)` and `(r2 mod m2)`
+// are simultaneously satisfiable iff `(r1 - r2) % gcd(m1, m2) == 0`; when they are, returns the
+// single equivalent residue mod lcm(m1, m2). Returns None if the two are mutually exclusive, i.e.
+// no value can ever satisfy both.
+fn merge_modular(a: &ModularCon, b: &ModularCon) -> Option<ModularCon> {
+    let (g, _, _) = extended_gcd(a.modulus, b.modulus);
+    if (b.residue - a.residue).rem_euclid(g) != 0 {
+        return None;
+    }
+    let m1 = a.modulus / g;
+    let m2 = b.modulus / g;
+    let (_, inv_m1, _) = extended_gcd(m1, m2);
+    let lcm = m1 * b.modulus;
+    let k = (b.residue - a.residue) / g * inv_m1;
+    let residue = (a.residue + a.modulus * k).rem_euclid(lcm);
+    Some(ModularCon { modulus: lcm, residue })
+}
+
+impl Constraint {
+    pub fn eval(&self, val: Int) -> ConstraintResult {
+        if let ConstraintResult::Failure(alt) = self.mag.eval(val) {
+            return ConstraintResult::Failure(alt);
+        }
+        for m in self.mods.iter() {
+            if let ConstraintResult::Failure(alt) = m.eval(val) {
+                return ConstraintResult::Failure(alt);
+            }
+        }
+        ConstraintResult::Success
+    }
+
+    // Fold a new modular precondition into this constraint, merging it with any existing one via
+    // CRT. Returns false (leaving `self` unchanged) if `con` is mutually exclusive with an
+    // existing modular precondition, meaning the whole constraint can never be satisfied.
+    pub fn add_modular(&mut self, con: ModularCon) -> bool {
+        match self.mods.first() {
+            None => {
+                self.mods.push(con);
+                true
+            }
+            Some(existing) => match merge_modular(existing, &con) {
+                Some(merged) => {
+                    self.mods[0] = merged;
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modular_eval() {
+        let con = ModularCon { modulus: 2, residue: 1 };
+        assert!(matches!(con.eval(7), ConstraintResult::Success));
+        assert!(matches!(con.eval(4), ConstraintResult::Failure(_)));
+    }
+
+    #[test]
+    fn test_modular_eval_failure_carries_observed_residue() {
+        let con = ModularCon { modulus: 5, residue: 0 };
+        match con.eval(7) {
+            ConstraintResult::Failure(alt) => {
+                assert_eq!(alt.mods, vec![ModularCon { modulus: 5, residue: 2 }]);
+            }
+            ConstraintResult::Success => panic!("expected failure"),
+        }
+    }
+
+    #[test]
+    fn test_merge_modular_compatible() {
+        // x % 2 == 1 and x % 3 == 2 together mean x % 6 == 5.
+        let a = ModularCon { modulus: 2, residue: 1 };
+        let b = ModularCon { modulus: 3, residue: 2 };
+        let merged = merge_modular(&a, &b).unwrap();
+        assert_eq!(merged, ModularCon { modulus: 6, residue: 5 });
+        for x in [5, 11, 17, -1] {
+            assert_eq!(x.rem_euclid(2), 1);
+            assert_eq!(x.rem_euclid(3), 2);
+            assert_eq!(x.rem_euclid(6), merged.residue);
+        }
+    }
+
+    #[test]
+    fn test_merge_modular_non_coprime_compatible() {
+        // x % 4 == 1 and x % 6 == 3: gcd(4,6) = 2, and (3 - 1) % 2 == 0, so compatible, merging
+        // to x % 12 == r for whichever r satisfies both.
+        let a = ModularCon { modulus: 4, residue: 1 };
+        let b = ModularCon { modulus: 6, residue: 3 };
+        let merged = merge_modular(&a, &b).unwrap();
+        assert_eq!(merged.modulus, 12);
+        assert_eq!(merged.residue.rem_euclid(4), 1);
+        assert_eq!(merged.residue.rem_euclid(6), 3);
+    }
+
+    #[test]
+    fn test_merge_modular_incompatible() {
+        // x % 2 == 1 (odd) and x % 4 == 0 (a multiple of 4, hence even) can never both hold.
+        let a = ModularCon { modulus: 2, residue: 1 };
+        let b = ModularCon { modulus: 4, residue: 0 };
+        assert_eq!(merge_modular(&a, &b), None);
+    }
+
+    #[test]
+    fn test_constraint_add_modular_merges_and_rejects() {
+        let mut con = Constraint { mag: MagnitudeCon::Unconstrained, mods: Vec::new() };
+        assert!(con.add_modular(ModularCon { modulus: 2, residue: 1 }));
+        assert!(con.add_modular(ModularCon { modulus: 3, residue: 2 }));
+        assert_eq!(con.mods, vec![ModularCon { modulus: 6, residue: 5 }]);
+
+        assert!(!con.add_modular(ModularCon { modulus: 4, residue: 0 }));
+        // Rejected merge leaves the constraint as it was.
+        assert_eq!(con.mods, vec![ModularCon { modulus: 6, residue: 5 }]);
+    }
+
+    #[test]
+    fn test_constraint_eval() {
+        let con = Constraint {
+            mag: MagnitudeCon::Min(0),
+            mods: vec![ModularCon { modulus: 2, residue: 0 }],
+        };
+        assert!(matches!(con.eval(4), ConstraintResult::Success));
+        assert!(matches!(con.eval(-1), ConstraintResult::Failure(_)));
+        assert!(matches!(con.eval(3), ConstraintResult::Failure(_)));
+    }
+}
@@ -9,7 +9,7 @@ use itertools::izip;
 
 use crate::program::{BigInt, Program, SmallInt, State};
 use crate::rule::{ApplyResult, Rule};
-use crate::state_diff::{StateDiff, StateDiffBig, StateDiffBound};
+use crate::state_diff::{Int, StateDiff, StateDiffBig, StateDiffBound};
 use crate::transcript::Trans;
 
 // Inductive Diff Rule based on a Trans.
@@ -98,6 +98,31 @@ impl DiffRule {
             None
         }
     }
+
+    // Are `self` and `other`'s deltas the same up to a nonzero integer scalar -- e.g. one rule
+    // draining a register by 1 per step and another draining it by 2 per step? Used to collapse
+    // rep-blocks whose DiffRules are "the same rule, applied a different number of times" into
+    // one equivalence class.
+    pub fn delta_is_scalar_multiple(&self, other: &DiffRule) -> bool {
+        scalar_multiple(&self.delta, &other.delta).is_some()
+            || scalar_multiple(&other.delta, &self.delta).is_some()
+    }
+}
+
+// If `candidate == base * k` for some integer k, returns k.
+fn scalar_multiple(base: &StateDiff, candidate: &StateDiff) -> Option<Int> {
+    match base.data.iter().position(|x| *x != 0) {
+        None => (candidate.data.iter().all(|x| *x == 0)).then_some(0),
+        Some(p) => {
+            if candidate.data[p] % base.data[p] != 0 {
+                return None;
+            }
+            let k = candidate.data[p] / base.data[p];
+            // k == 0 would mean `candidate` is the zero vector while `base` (having a nonzero
+            // entry at p) isn't -- not a genuine scalar relationship, just two unrelated deltas.
+            (k != 0 && base * k == *candidate).then_some(k)
+        }
+    }
 }
 
 impl Rule for DiffRule {
@@ -455,4 +480,28 @@ mod tests {
             assert_eq!(rule.delta, sd![0, 0, 0, 0, 1, -1]);
         }
     }
+
+    #[test]
+    fn test_delta_is_scalar_multiple() {
+        let rule_with_delta = |delta: StateDiff| DiffRule {
+            min: sd![0, 0],
+            max: sdb![Infinity, Infinity],
+            delta,
+            num_steps: 1,
+        };
+
+        let a = rule_with_delta(sd![1, -1]);
+        let b = rule_with_delta(sd![3, -3]);
+        let c = rule_with_delta(sd![-2, 2]);
+        let d = rule_with_delta(sd![1, -2]);
+        let zero = rule_with_delta(sd![0, 0]);
+
+        assert!(a.delta_is_scalar_multiple(&a));
+        assert!(a.delta_is_scalar_multiple(&b));
+        assert!(b.delta_is_scalar_multiple(&a));
+        assert!(a.delta_is_scalar_multiple(&c));
+        assert!(!a.delta_is_scalar_multiple(&d));
+        assert!(zero.delta_is_scalar_multiple(&zero));
+        assert!(!a.delta_is_scalar_multiple(&zero));
+    }
 }
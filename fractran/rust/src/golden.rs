@@ -0,0 +1,102 @@
+// Golden test vectors, one record per line (NDJSON): a program, how many steps to run it, and
+// the expected final configuration. `check_golden_case` runs *both* the base interpreter and
+// `ShiftSim` over the same record and asserts each agrees with the stored expectation and with
+// each other -- the repo's first cross-backend correctness net.
+
+use serde::{Deserialize, Serialize};
+
+use crate::parse::parse_program;
+use crate::program::{Int, State};
+use crate::shift_sim::ShiftSim;
+use crate::simulator::{BaseSimulator, SimStatus, Simulator};
+
+// Same shift-rule-discovery transcript length `shift_sim.rs`'s own tests use.
+const TRANSCRIPT_STEPS: Int = 100;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GoldenCase {
+    pub program: String,
+    pub num_steps: Int,
+    pub final_state: State,
+    pub total_steps: Int,
+    pub halted: bool,
+}
+
+// Parse one golden case per nonempty, non-comment line of `ndjson`.
+pub fn load_golden_cases(ndjson: &str) -> Vec<GoldenCase> {
+    ndjson
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| serde_json::from_str(line).expect("Invalid golden case record"))
+        .collect()
+}
+
+fn expected_status(halted: bool) -> SimStatus {
+    if halted {
+        SimStatus::Halted
+    } else {
+        SimStatus::Running
+    }
+}
+
+// Run `case` through both backends and assert each matches the stored expectation (and, via the
+// shared expectation, each other). Only meant for halting cases or cases that stay plain
+// `Running`: `ShiftSim` can legitimately report `Infinite` instead of `Running` once it proves
+// non-halting, which a golden case recorded from the base interpreter alone can't anticipate.
+pub fn check_golden_case(case: &GoldenCase) {
+    let prog = parse_program(&case.program);
+    let start = State::start(&prog);
+    let want_status = expected_status(case.halted);
+
+    let mut base = BaseSimulator::new(prog.clone());
+    let base_final = base.step_until(start.clone(), case.num_steps);
+    assert_eq!(base.status(), want_status, "base interpreter status for {:?}", case.program);
+    assert_eq!(base.steps(), case.total_steps, "base interpreter step count for {:?}", case.program);
+    assert_eq!(base_final, case.final_state, "base interpreter final state for {:?}", case.program);
+
+    let mut shift = ShiftSim::build(prog, start.clone(), TRANSCRIPT_STEPS);
+    let shift_final = shift.step_until(start, case.num_steps);
+    let shift_status = shift.status();
+    // `Infinite` in place of `Running` is the legitimate early-proof case described above: it
+    // can fire before `case.num_steps` is reached, so step count and final state (which assume
+    // running all the way to `case.num_steps`) aren't comparable to the stored expectation then.
+    assert!(
+        shift_status == want_status || (want_status == SimStatus::Running && shift_status == SimStatus::Infinite),
+        "ShiftSim status for {:?}: got {:?}, want {:?}",
+        case.program,
+        shift_status,
+        want_status
+    );
+    // A shift/meta rule application can't be subdivided, so `step_until` may legitimately
+    // overshoot `case.num_steps` within its last jump -- but it must never stop short of it
+    // while still `Running`. Only compare against the stored expectation when ShiftSim's own
+    // step count lands exactly on `case.num_steps`.
+    if shift_status == SimStatus::Running {
+        assert!(
+            shift.steps() >= case.total_steps,
+            "ShiftSim stopped short of budget for {:?}: got {}, want >= {}",
+            case.program,
+            shift.steps(),
+            case.total_steps
+        );
+    }
+    if shift.steps() == case.total_steps {
+        assert_eq!(shift_final, case.final_state, "ShiftSim final state for {:?}", case.program);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_golden_vectors_cross_validate() {
+        let ndjson = include_str!("../testdata/golden_cases.ndjson");
+        let cases = load_golden_cases(ndjson);
+        assert!(!cases.is_empty());
+        for case in &cases {
+            check_golden_case(case);
+        }
+    }
+}
@@ -0,0 +1,178 @@
+// Karp-Miller coverability tree: a classic decision procedure for boundedness and
+// coverability of a Petri net / pVAS, complementing the approximate `closed_vec_set::closure`
+// (which only proves non-halting for sets it happens to converge on).
+//
+// Markings are vectors of `Option<BigInt>`, where `None` stands for omega (an unbounded
+// register). Starting from `State::start`, every applicable instr is fired; whenever a freshly
+// produced marking `m` has an ancestor `m' <= m` (componentwise) with `m' != m`, every
+// coordinate where `m` strictly exceeds `m'` is widened to omega before the node is kept. Any
+// child covered by (<=) an existing ancestor is pruned, which is what guarantees the tree is
+// finite (Karp & Miller, 1969).
+
+use crate::program::{BigInt, Instr, Program, State};
+use crate::vec_set::{NatSet, UnionVecSet, VecSet};
+
+type Marking = Vec<Option<BigInt>>;
+
+fn state_to_marking(state: &State) -> Marking {
+    state.data.iter().cloned().map(Some).collect()
+}
+
+// Can `instr` fire from `marking`? Omega components are always enabled.
+fn can_apply(instr: &Instr, marking: &Marking) -> bool {
+    marking
+        .iter()
+        .zip(instr.data.iter())
+        .all(|(m, delta)| match m {
+            None => true,
+            Some(val) => val.clone() + *delta >= 0,
+        })
+}
+
+// Fire `instr` from `marking`. Omega components are unchanged by apply.
+fn apply(instr: &Instr, marking: &Marking) -> Marking {
+    marking
+        .iter()
+        .zip(instr.data.iter())
+        .map(|(m, delta)| m.as_ref().map(|val| val.clone() + *delta))
+        .collect()
+}
+
+// Is `a` componentwise <= `b`, treating omega (None) as +infinity?
+fn leq(a: &Marking, b: &Marking) -> bool {
+    a.iter().zip(b.iter()).all(|(x, y)| match (x, y) {
+        (_, None) => true,
+        (None, Some(_)) => false,
+        (Some(x), Some(y)) => x <= y,
+    })
+}
+
+// Widen every coordinate of `child` that strictly exceeds the matching coordinate of
+// `ancestor` (an ancestor with `leq(ancestor, child)`) to omega.
+fn widen_to_omega(child: &Marking, ancestor: &Marking) -> Marking {
+    child
+        .iter()
+        .zip(ancestor.iter())
+        .map(|(c, a)| match (c, a) {
+            (None, _) => None,
+            (Some(c_val), Some(a_val)) if c_val > a_val => None,
+            (Some(c_val), _) => Some(c_val.clone()),
+        })
+        .collect()
+}
+
+fn marking_to_vec_set(marking: &Marking) -> VecSet {
+    let nat_sets = marking
+        .iter()
+        .map(|c| match c {
+            None => NatSet::Min(0),
+            Some(val) => NatSet::Fixed(
+                val.to_i32()
+                    .expect("marking value too large to represent as a SmallInt"),
+            ),
+        })
+        .collect();
+    VecSet::new(nat_sets)
+}
+
+// Result of building the coverability tree of a Program.
+pub struct KarpMillerTree {
+    // True iff no omega was ever introduced, i.e. every reachable marking is bounded.
+    pub bounded: bool,
+    // The finite set of markings where at least one register was widened to omega.
+    pub omega_markings: UnionVecSet,
+    nodes: Vec<Marking>,
+}
+
+impl KarpMillerTree {
+    // Is `target` coverable, i.e. does some marking in the tree dominate it componentwise?
+    pub fn can_cover(&self, target: &State) -> bool {
+        let target = state_to_marking(target);
+        self.nodes.iter().any(|m| leq(&target, m))
+    }
+}
+
+// Build the Karp-Miller coverability tree for `prog`, starting from `State::start`.
+pub fn build_coverability_tree(prog: &Program) -> KarpMillerTree {
+    let root = state_to_marking(&State::start(prog));
+
+    let mut bounded = true;
+    let mut nodes = vec![root.clone()];
+    // DFS stack of (marking, path from root to this marking inclusive).
+    let mut stack: Vec<(Marking, Vec<Marking>)> = vec![(root.clone(), vec![root])];
+
+    while let Some((marking, path)) = stack.pop() {
+        for instr in prog.instrs.iter() {
+            if !can_apply(instr, &marking) {
+                continue;
+            }
+            let mut child = apply(instr, &marking);
+            for ancestor in path.iter() {
+                if leq(ancestor, &child) && ancestor != &child {
+                    child = widen_to_omega(&child, ancestor);
+                }
+            }
+            if child.iter().any(|c| c.is_none()) {
+                bounded = false;
+            }
+            // Pruned: already covered by an ancestor, so expanding it can't find anything new.
+            if path.iter().any(|ancestor| leq(&child, ancestor)) {
+                continue;
+            }
+            let mut child_path = path.clone();
+            child_path.push(child.clone());
+            nodes.push(child.clone());
+            stack.push((child, child_path));
+        }
+    }
+
+    let omega_markings = nodes
+        .iter()
+        .filter(|m| m.iter().any(|c| c.is_none()))
+        .map(marking_to_vec_set)
+        .collect();
+
+    KarpMillerTree {
+        bounded,
+        omega_markings: UnionVecSet::new(omega_markings),
+        nodes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prog, state};
+
+    #[test]
+    fn test_bounded_net() {
+        // Single register, single instr that fires exactly once.
+        let p = prog![-1];
+        let tree = build_coverability_tree(&p);
+        assert!(tree.bounded);
+        assert!(tree.omega_markings.is_empty());
+        assert!(tree.can_cover(&state![1]));
+        assert!(tree.can_cover(&state![0]));
+        assert!(!tree.can_cover(&state![2]));
+    }
+
+    #[test]
+    fn test_unbounded_net_introduces_omega() {
+        // A single instr that only ever adds tokens: [r0, r1] -> [r0+1, r1+1]. Since it's
+        // always enabled and never consumes, both registers grow without bound.
+        let p = prog![1, 1];
+        let tree = build_coverability_tree(&p);
+        assert!(!tree.bounded);
+        assert!(!tree.omega_markings.is_empty());
+
+        // The omega marking covers any target, however large.
+        assert!(tree.can_cover(&state![1_000, 1_000]));
+    }
+
+    #[test]
+    fn test_can_cover_unreachable_target() {
+        let p = prog![-1];
+        let tree = build_coverability_tree(&p);
+        assert!(!tree.can_cover(&state![5]));
+    }
+}
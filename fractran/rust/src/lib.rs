@@ -0,0 +1,18 @@
+pub mod closed_vec_set;
+pub mod constraints;
+pub mod diff_rule;
+pub mod golden;
+pub mod int_range;
+pub mod karp_miller;
+pub mod parse;
+pub mod pnml;
+pub mod program;
+pub mod pvas;
+pub mod rule;
+pub mod shift_sim;
+pub mod simulator;
+pub mod state_diff;
+pub mod tandem_repeat;
+pub mod transcript;
+pub mod union_find;
+pub mod vec_set;
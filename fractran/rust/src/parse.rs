@@ -1,31 +1,73 @@
-use crate::program::{Int, Program, Rule};
+use crate::program::{Instr, Program, SmallInt};
 use primal::Primes;
 use prime_factorization::Factorization;
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{self, BufRead};
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
 
-// Parse a Fractran program and convert into vector form.
+// Parse a single program line, auto-detecting its syntax: a `/` anywhere on the line means it's
+// a Fractran fraction list (e.g. "[2/45, 25/6]"); otherwise it's the native signed-delta matrix
+// form the rest of the codebase (and the `prog!` macro) use directly (e.g. "-1, 5, 0; 0, -1, 3"),
+// parsed with no factorization at all. The matrix form is strictly more expressive: it can
+// express rules that both consume and produce on the same register, which no positive Fractran
+// fraction can.
 pub fn parse_program(program_str: &str) -> Program {
-    // 1. Clean and split string
+    let instrs = if program_str.contains('/') {
+        parse_fractran(program_str)
+    } else {
+        parse_matrix(program_str)
+    };
+    Program { instrs }
+}
+
+// Parse the native signed-delta matrix form: rows of comma-separated signed integers separated
+// by `;`, one row per Instr -- the same representation the `prog!` macro builds.
+//
+// Every row must have the same width: `Program::num_registers` takes its answer from just the
+// first Instr, so a row of a different length would silently desync register indices (or panic
+// with an out-of-bounds zip) the first time some other row's extra/missing column is touched.
+// Reject that up front instead, the same way `compile_to_binary` rejects inconsistent rows.
+fn parse_matrix(program_str: &str) -> Vec<Instr> {
+    let instrs: Vec<Instr> = program_str
+        .split(';')
+        .map(str::trim)
+        .filter(|row| !row.is_empty())
+        .map(|row| {
+            let data = row
+                .split(',')
+                .map(|x| x.trim().parse().expect("Invalid matrix entry"))
+                .collect();
+            Instr::new(data)
+        })
+        .collect();
+
+    let dims = instrs.first().map_or(0, Instr::num_registers);
+    assert!(
+        instrs.iter().all(|instr| instr.num_registers() == dims),
+        "program matrix has rows of inconsistent width"
+    );
+    instrs
+}
+
+// Parse a Fractran fraction-list line (e.g. "[2/45, 25/6]") into Instr rows: factorize every
+// numerator/denominator, map each prime encountered to a register column (2 -> 0, 3 -> 1, 5 -> 2,
+// ... up to the largest prime used), and build one row per fraction from its prime multiplicity
+// deltas.
+fn parse_fractran(program_str: &str) -> Vec<Instr> {
     let clean_str = program_str.replace(['[', ']', ' '], "");
     let parts: Vec<&str> = clean_str.split(',').collect();
 
-    // 2. Parse fractions and find max prime
     let mut rules_fractions: Vec<(u128, u128)> = Vec::new();
     let mut max_prime_found: u128 = 2;
 
-    for part in parts {
+    for part in &parts {
         let frac: Vec<&str> = part.split('/').collect();
         let num: u128 = frac[0].parse().expect("Invalid numerator");
         let den: u128 = frac[1].parse().expect("Invalid denominator");
         rules_fractions.push((num, den));
 
-        // Check factors to find the largest prime needed for dimensions
-        // We iterate the factors to find the max
         let num_factors = Factorization::run(num);
         let den_factors = Factorization::run(den);
-
         if let Some(&max) = num_factors.factors.iter().max() {
             if max > max_prime_found {
                 max_prime_found = max;
@@ -38,12 +80,8 @@ pub fn parse_program(program_str: &str) -> Program {
         }
     }
 
-    // 3. Generate prime map (Prime -> Index) using `primal` crate
-    // We map standard primes 2->0, 3->1, 5->2... up to max_prime_found
     let mut prime_map = HashMap::new();
     let mut dims = 0;
-
-    // Primes::all() returns an iterator of usize. We cast to u128.
     for (i, p) in Primes::all().enumerate() {
         let p_u128 = p as u128;
         prime_map.insert(p_u128, i);
@@ -53,30 +91,23 @@ pub fn parse_program(program_str: &str) -> Program {
         }
     }
 
-    // 4. Build Matrix
-    let mut rules: Vec<Rule> = Vec::new();
-
-    for (num, den) in rules_fractions.iter() {
-        let mut rule = vec![0 as Int; dims];
-        // Handle Numerator (Additions)
-        let num_factors = Factorization::run(*num);
-        for p in num_factors.factors {
-            if let Some(&col) = prime_map.get(&p) {
-                rule[col] += 1;
+    rules_fractions
+        .iter()
+        .map(|(num, den)| {
+            let mut delta = vec![0 as SmallInt; dims];
+            for p in Factorization::run(*num).factors {
+                if let Some(&col) = prime_map.get(&p) {
+                    delta[col] += 1;
+                }
             }
-        }
-
-        // Handle Denominator (Subtractions)
-        let den_factors = Factorization::run(*den);
-        for p in den_factors.factors {
-            if let Some(&col) = prime_map.get(&p) {
-                rule[col] -= 1;
+            for p in Factorization::run(*den).factors {
+                if let Some(&col) = prime_map.get(&p) {
+                    delta[col] -= 1;
+                }
             }
-        }
-        rules.push(Rule::new(rule));
-    }
-
-    Program { rules }
+            Instr::new(delta)
+        })
+        .collect()
 }
 
 // Load all program strings from a file (without parsing).
@@ -112,3 +143,202 @@ pub fn load_program(filename_record: &str) -> Option<Program> {
     let prog_str = lines.iter().nth(record_num)?;
     Some(parse_program(prog_str))
 }
+
+// --- Compact binary program database ---------------------------------------------------------
+//
+// `parse_program` re-runs prime factorization on every fraction on every load, and
+// `load_program` re-reads and re-parses the whole text file just to reach one record -- for
+// million-program sweeps this dominates startup. The format below factorizes each line exactly
+// once, then stores every program as a packed run of fixed-width little-endian deltas (the same
+// flat, row-major layout `PVAS` uses for its rule matrix), plus an offset table so a later load
+// can seek straight to record `n` and skip parsing entirely.
+//
+// Layout:
+//   magic:        4 bytes, b"FRCB"
+//   version:      u32 LE
+//   num_records:  u32 LE
+//   offsets:      num_records * u64 LE -- byte offset of each record from the start of the file
+//   records:      for each record, in order:
+//                   dims:      u32 LE
+//                   num_rules: u32 LE
+//                   deltas:    num_rules * dims SmallInt (i32 LE), row-major (one row per Instr)
+
+const BINARY_MAGIC: &[u8; 4] = b"FRCB";
+const BINARY_VERSION: u32 = 1;
+
+// Compile `lines` (one program per line, either syntax `parse_program` accepts) into the binary
+// container format described above, writing it to `out`. Parses (and, for the Fractran syntax,
+// factorizes) each line exactly once.
+//
+// Every `Instr` in a record must share the same `num_registers()`: the offset table below is
+// computed from `dims * instrs.len()`, taking `dims` from just the first `Instr`, while the
+// record body writes each `Instr`'s own `data` -- a record with inconsistent row widths would
+// write more or fewer bytes than the offset table promised, desyncing every later record's
+// offset. Reject that case up front instead of silently corrupting the file.
+pub fn compile_to_binary(lines: &[String], out: &mut impl Write) -> io::Result<()> {
+    let records: Vec<Vec<Instr>> = lines.iter().map(|line| parse_program(line).instrs).collect();
+
+    let mut dims: Vec<usize> = Vec::with_capacity(records.len());
+    for instrs in &records {
+        let record_dims = instrs.first().map_or(0, Instr::num_registers);
+        if instrs.iter().any(|instr| instr.num_registers() != record_dims) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "program record has Instr rows of inconsistent width",
+            ));
+        }
+        dims.push(record_dims);
+    }
+
+    out.write_all(BINARY_MAGIC)?;
+    out.write_all(&BINARY_VERSION.to_le_bytes())?;
+    out.write_all(&(records.len() as u32).to_le_bytes())?;
+
+    // Offset table: filled in with a placeholder pass, then patched with real offsets once we
+    // know each record's encoded length.
+    let header_len = 4 + 4 + 4;
+    let offsets_len = records.len() * 8;
+    let mut offset = (header_len + offsets_len) as u64;
+    let mut offsets = Vec::with_capacity(records.len());
+    for (instrs, &record_dims) in records.iter().zip(&dims) {
+        offsets.push(offset);
+        offset += (4 + 4 + record_dims * instrs.len() * 4) as u64;
+    }
+    for offset in &offsets {
+        out.write_all(&offset.to_le_bytes())?;
+    }
+
+    for (instrs, &record_dims) in records.iter().zip(&dims) {
+        out.write_all(&(record_dims as u32).to_le_bytes())?;
+        out.write_all(&(instrs.len() as u32).to_le_bytes())?;
+        for instr in instrs {
+            for delta in &instr.data {
+                out.write_all(&delta.to_le_bytes())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Load just record `record_num` out of the binary database at `path`, seeking past every other
+// record instead of parsing them. Mirrors `split_filename_record`'s `path:record_num` syntax.
+pub fn load_program_binary(path_record: &str) -> Option<Program> {
+    let (path, record_num) = split_filename_record(path_record);
+    let mut file = File::open(path).ok()?;
+
+    let mut magic = [0u8; 4];
+    file.read_exact(&mut magic).ok()?;
+    if &magic != BINARY_MAGIC {
+        return None;
+    }
+    let mut u32_buf = [0u8; 4];
+    file.read_exact(&mut u32_buf).ok()?;
+    let version = u32::from_le_bytes(u32_buf);
+    if version != BINARY_VERSION {
+        return None;
+    }
+    file.read_exact(&mut u32_buf).ok()?;
+    let num_records = u32::from_le_bytes(u32_buf) as usize;
+    if record_num >= num_records {
+        return None;
+    }
+
+    file.seek(SeekFrom::Current((record_num * 8) as i64)).ok()?;
+    let mut u64_buf = [0u8; 8];
+    file.read_exact(&mut u64_buf).ok()?;
+    let record_offset = u64::from_le_bytes(u64_buf);
+
+    file.seek(SeekFrom::Start(record_offset)).ok()?;
+    file.read_exact(&mut u32_buf).ok()?;
+    let dims = u32::from_le_bytes(u32_buf) as usize;
+    file.read_exact(&mut u32_buf).ok()?;
+    let num_rules = u32::from_le_bytes(u32_buf) as usize;
+
+    let mut instrs = Vec::with_capacity(num_rules);
+    let mut delta_buf = [0u8; 4];
+    for _ in 0..num_rules {
+        let mut data = Vec::with_capacity(dims);
+        for _ in 0..dims {
+            file.read_exact(&mut delta_buf).ok()?;
+            data.push(SmallInt::from_le_bytes(delta_buf));
+        }
+        instrs.push(Instr::new(data));
+    }
+    Some(Program { instrs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prog;
+
+    #[test]
+    fn test_parse_program_matrix_form() {
+        let prog = parse_program("-1, 5, 0; 0, -1, 3; 0, 0, -1");
+        assert_eq!(
+            prog.instrs,
+            prog![-1,  5,  0;
+                   0, -1,  3;
+                   0,  0, -1]
+            .instrs
+        );
+    }
+
+    #[test]
+    fn test_parse_program_matrix_form_allows_same_register_consume_and_produce() {
+        // Fractran fractions can't express a rule that both subtracts and adds on register 0,
+        // but the matrix form can.
+        let prog = parse_program("-2, 1");
+        assert_eq!(prog.instrs, vec![Instr::new(vec![-2, 1])]);
+    }
+
+    #[test]
+    #[should_panic(expected = "inconsistent width")]
+    fn test_parse_program_matrix_form_rejects_inconsistent_row_widths() {
+        // Same malformed shape `test_compile_to_binary_rejects_inconsistent_row_widths` covers
+        // downstream of here (3 entries, then 2).
+        parse_program("-1, 5, 0; 0, -1");
+    }
+
+    #[test]
+    fn test_parse_program_fractran_form() {
+        let prog = parse_program("[3/2, 2/3]");
+        assert_eq!(
+            prog.instrs,
+            vec![Instr::new(vec![-1, 1]), Instr::new(vec![1, -1])]
+        );
+    }
+
+    #[test]
+    fn test_compile_and_load_binary_round_trips_parse_program() {
+        let lines = vec!["2/1,1/2".to_string(), "3/2,1/3,5/1".to_string()];
+        let mut buf = Vec::new();
+        compile_to_binary(&lines, &mut buf).unwrap();
+
+        std::fs::write("/tmp/test_compile_to_binary.frcb", &buf).unwrap();
+        for (i, line) in lines.iter().enumerate() {
+            let loaded =
+                load_program_binary(&format!("/tmp/test_compile_to_binary.frcb:{}", i)).unwrap();
+            assert_eq!(loaded.instrs, parse_program(line).instrs);
+        }
+    }
+
+    #[test]
+    fn test_compile_to_binary_rejects_inconsistent_row_widths() {
+        // The matrix form parses each row independently, so a malformed program with rows of
+        // different lengths (3 entries, then 2) makes it through `parse_program` uncaught.
+        let lines = vec!["-1, 5, 0; 0, -1".to_string()];
+        let mut buf = Vec::new();
+        let err = compile_to_binary(&lines, &mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_load_program_binary_rejects_out_of_range_record() {
+        let lines = vec!["2/1,1/2".to_string()];
+        let mut buf = Vec::new();
+        compile_to_binary(&lines, &mut buf).unwrap();
+        std::fs::write("/tmp/test_compile_to_binary_oob.frcb", &buf).unwrap();
+        assert!(load_program_binary("/tmp/test_compile_to_binary_oob.frcb:1").is_none());
+    }
+}
@@ -0,0 +1,205 @@
+// Round-trip a Program/State to and from PNML (place/transition net) documents, so nets
+// authored or transformed by external tools like Tapaal/LoLA can be brought back in for
+// simulation and for the `closed_vec_set` deciders.
+
+use std::collections::HashMap;
+
+use crate::program::{BigInt, Instr, Program, SmallInt, State};
+
+// Render `prog`/`state` as a minimal PNML P/T-net document: one place per register (named by
+// register index, carrying `state`'s value as its initial marking), one transition per instr,
+// and an arc per nonzero delta (place -> transition to consume, transition -> place to produce).
+pub fn to_pnml(prog: &Program, state: &State) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<pnml xmlns=\"http://www.pnml.org/version-2009/grammar/pnml\">\n");
+    out.push_str(" <net id=\"pvas_net\" type=\"http://www.pnml.org/version-2009/grammar/ptnet\">\n");
+    out.push_str("  <page id=\"page0\">\n");
+
+    for (i, init_val) in state.data.iter().enumerate() {
+        out.push_str(&format!("   <place id=\"p{}\">\n", i));
+        out.push_str(&format!("    <name><text>p{}</text></name>\n", i));
+        out.push_str(&format!(
+            "    <initialMarking><text>{}</text></initialMarking>\n",
+            init_val
+        ));
+        out.push_str(&format!(
+            "    <graphics><position x=\"{}\" y=\"100\"/></graphics>\n",
+            100 + (i * 80)
+        ));
+        out.push_str("   </place>\n");
+    }
+
+    for r in 0..prog.num_instrs() {
+        out.push_str(&format!("   <transition id=\"t{}\">\n", r));
+        out.push_str(&format!("    <name><text>t{}</text></name>\n", r));
+        out.push_str(&format!(
+            "    <graphics><position x=\"{}\" y=\"200\"/></graphics>\n",
+            100 + (r * 80)
+        ));
+        out.push_str("   </transition>\n");
+    }
+
+    let mut arc_num = 0;
+    for (instr_num, instr) in prog.instrs.iter().enumerate() {
+        for (place_num, delta) in instr.data.iter().enumerate() {
+            if *delta < 0 {
+                out.push_str(&format!(
+                    "   <arc id=\"a{}\" source=\"p{}\" target=\"t{}\">\n",
+                    arc_num, place_num, instr_num
+                ));
+                out.push_str(&format!(
+                    "    <inscription><text>{}</text></inscription>\n",
+                    -delta
+                ));
+                out.push_str("   </arc>\n");
+                arc_num += 1;
+            } else if *delta > 0 {
+                out.push_str(&format!(
+                    "   <arc id=\"a{}\" source=\"t{}\" target=\"p{}\">\n",
+                    arc_num, instr_num, place_num
+                ));
+                out.push_str(&format!(
+                    "    <inscription><text>{}</text></inscription>\n",
+                    delta
+                ));
+                out.push_str("   </arc>\n");
+                arc_num += 1;
+            }
+        }
+    }
+
+    out.push_str("  </page>\n");
+    out.push_str(" </net>\n");
+    out.push_str("</pnml>\n");
+    out
+}
+
+// Pull the `name="..."` attribute value out of a tag's opening fragment.
+fn extract_attr<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!("{}=\"", name);
+    let start = tag.find(&needle)? + needle.len();
+    let end = start + tag[start..].find('"')?;
+    Some(&tag[start..end])
+}
+
+// Pull the text content of `<tag><text>...</text></tag>` out of an element body.
+fn extract_tag_text(body: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let tag_start = body.find(&open)? + open.len();
+    let tag_body = &body[tag_start..];
+    let text_start = tag_body.find("<text>")? + "<text>".len();
+    let text_end = tag_body[text_start..].find("</text>")?;
+    Some(tag_body[text_start..text_start + text_end].to_string())
+}
+
+// Parse a place/transition id like "p3" or "t12" into its numeric suffix.
+fn parse_id_num(id: &str, prefix: char) -> usize {
+    id.strip_prefix(prefix)
+        .unwrap_or_else(|| panic!("expected id starting with '{}', got {:?}", prefix, id))
+        .parse()
+        .unwrap_or_else(|_| panic!("expected numeric id suffix, got {:?}", id))
+}
+
+// Parse a `<pnml>` P/T-net document (places, transitions, and arcs with `<inscription>`
+// weights) back into a `Program` and its start `State`. Places are sorted by their `pN` id to
+// match `to_pnml`'s layout, so register indices round-trip exactly.
+pub fn parse_pnml(xml: &str) -> (Program, State) {
+    let mut place_ids: Vec<usize> = xml
+        .split("<place ")
+        .skip(1)
+        .map(|chunk| parse_id_num(extract_attr(chunk, "id").expect("place missing id"), 'p'))
+        .collect();
+    place_ids.sort();
+    let place_index: HashMap<usize, usize> = place_ids
+        .iter()
+        .enumerate()
+        .map(|(reg, &id)| (id, reg))
+        .collect();
+    let num_regs = place_ids.len();
+
+    let mut initial = vec![BigInt::from(0); num_regs];
+    for chunk in xml.split("<place ").skip(1) {
+        let end = chunk.find("</place>").unwrap_or(chunk.len());
+        let body = &chunk[..end];
+        let id = parse_id_num(extract_attr(chunk, "id").expect("place missing id"), 'p');
+        let marking: i64 = extract_tag_text(body, "initialMarking")
+            .map(|s| s.trim().parse().expect("invalid initialMarking"))
+            .unwrap_or(0);
+        initial[place_index[&id]] = BigInt::from(marking);
+    }
+
+    let mut trans_ids: Vec<usize> = xml
+        .split("<transition ")
+        .skip(1)
+        .map(|chunk| parse_id_num(extract_attr(chunk, "id").expect("transition missing id"), 't'))
+        .collect();
+    trans_ids.sort();
+    let trans_index: HashMap<usize, usize> = trans_ids
+        .iter()
+        .enumerate()
+        .map(|(instr_num, &id)| (id, instr_num))
+        .collect();
+
+    let mut deltas: Vec<Vec<SmallInt>> = vec![vec![0; num_regs]; trans_ids.len()];
+    for chunk in xml.split("<arc ").skip(1) {
+        let end = chunk.find("</arc>").unwrap_or(chunk.len());
+        let body = &chunk[..end];
+        let source = extract_attr(chunk, "source").expect("arc missing source");
+        let target = extract_attr(chunk, "target").expect("arc missing target");
+        let weight: SmallInt = extract_tag_text(body, "inscription")
+            .map(|s| s.trim().parse().expect("invalid inscription"))
+            .unwrap_or(1);
+
+        if let Some(place) = source.strip_prefix('p') {
+            // Consume arc: place -> transition.
+            let reg = place_index[&place.parse().expect("invalid place id")];
+            let instr_num = trans_index[&parse_id_num(target, 't')];
+            deltas[instr_num][reg] -= weight;
+        } else {
+            // Produce arc: transition -> place.
+            let instr_num = trans_index[&parse_id_num(source, 't')];
+            let reg = place_index[&target.strip_prefix('p').expect("invalid arc endpoint").parse().expect("invalid place id")];
+            deltas[instr_num][reg] += weight;
+        }
+    }
+
+    let instrs = deltas.into_iter().map(Instr::new).collect();
+    (Program { instrs }, State::new(initial))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prog, state};
+
+    #[test]
+    fn test_round_trip_collatz_like_program() {
+        // Size 14 champion -- Collatz-like Fractran program.
+        let prog = prog![-1,  5,  0;
+                           0, -1,  3;
+                           0,  0, -1];
+        let start_state = state![1, 0, 0];
+
+        let xml = to_pnml(&prog, &start_state);
+        let (parsed_prog, parsed_state) = parse_pnml(&xml);
+
+        assert_eq!(parsed_prog.instrs, prog.instrs);
+        assert_eq!(parsed_state, start_state);
+    }
+
+    #[test]
+    fn test_round_trip_preserves_zero_delta_registers() {
+        // Register 1 never appears in any arc for instr 0 (delta 0), so it must still come back
+        // as a register (not silently dropped) with a zero delta.
+        let prog = prog![-1,  0;
+                           1, -1];
+        let start_state = state![2, 0];
+
+        let xml = to_pnml(&prog, &start_state);
+        let (parsed_prog, parsed_state) = parse_pnml(&xml);
+
+        assert_eq!(parsed_prog.instrs, prog.instrs);
+        assert_eq!(parsed_state, start_state);
+    }
+}
@@ -1,11 +1,13 @@
 use rug;
+use serde::{Deserialize, Serialize};
 
 // Small int and big int
 pub type SmallInt = i32;
+pub type Int = i64;
 pub type BigInt = rug::Integer;
 
 // Fractran/pVAS configuration state
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct State {
     pub data: Vec<BigInt>,
 }
@@ -21,7 +23,7 @@ pub struct Program {
     pub instrs: Vec<Instr>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct SimResult {
     pub halted: bool,
     pub total_steps: usize,
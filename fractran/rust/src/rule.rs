@@ -7,7 +7,13 @@ pub enum ApplyResult {
     // Rule does not apply at all.
     None,
     // Rule applies a finite number of times.
-    Some { num_apps: BigInt, result: State },
+    Some {
+        num_apps: BigInt,
+        result: State,
+        // Number of underlying Fractran steps that these `num_apps` repetitions
+        // correspond to, so accelerated runs stay comparable to a plain simulation.
+        base_steps: BigInt,
+    },
     // Rule applies infinitely (proof of non-halting).
     Infinite,
 }
@@ -3,9 +3,13 @@
 use std::collections::HashSet;
 
 use crate::diff_rule::DiffRule;
-use crate::program::{Int, Program, State};
+use crate::program::{BigInt, Int, Program, State};
 use crate::rule::{ApplyResult, Rule};
-use crate::tandem_repeat::find_rep_blocks;
+use crate::simulator::Simulator;
+// Re-exported so existing `fractran::shift_sim::SimStatus` callers keep working now that
+// `SimStatus` lives in `simulator` as the shared `Simulator` trait's status type.
+pub use crate::simulator::SimStatus;
+use crate::tandem_repeat::{find_rep_blocks, ToStringVec, DEFAULT_MAX_WINDOW};
 use crate::transcript::{transcript, Trans};
 
 
@@ -24,36 +28,278 @@ fn find_shift_rules(prog: &Program, state: State, transcript_steps: Int) -> Vec<
         .collect()
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub enum SimStatus {
-    Running,
-    Halted,
-    Infinite,
+// One entry in the higher-level "meta transcript" of which shift rule fired at each
+// sim_step. `rule_id == shift_rules.len()` is a sentinel meaning "no shift rule applied,
+// fell back to a single raw Program step". Magnitude (num_apps) is tracked separately in
+// `ShiftSim::meta_num_apps`, since repeat detection only cares about which rule fired.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MetaEvent {
+    rule_id: usize,
+}
+
+impl ToStringVec for MetaEvent {
+    fn to_string_one(&self) -> String {
+        self.rule_id.to_string()
+    }
+
+    fn to_string_vec(xs: &Vec<Self>) -> String {
+        xs.iter().map(|x| x.to_string_one()).collect::<Vec<_>>().join(",")
+    }
+}
+
+// A "meta" acceleration rule discovered by finding a tandem repeat within the meta
+// transcript: a fixed sequence of shift rules (`rule_ids`) firing over and over, where the
+// num_apps of each firing grows arithmetically from one repetition to the next (as is
+// typical when a shift rule itself sits inside an outer counter loop). The net StateDiff
+// contributed by repetition `r` (0-indexed from the first repetition `find_meta_rules` fit
+// the progression to) is `delta0 + r*delta1`, and the base Fractran steps it corresponds to
+// are `base_steps0 + r*base_steps1`. `rep_offset` is how many repetitions of this block are
+// already reflected in the live state by the time the rule is discovered -- the *next*
+// repetition to apply is `r = rep_offset`, not `r = 0`. See `apply_meta_k`.
+#[derive(Debug, Clone)]
+struct MetaRule {
+    rule_ids: Vec<usize>,
+    delta0: Vec<BigInt>,
+    delta1: Vec<BigInt>,
+    base_steps0: BigInt,
+    base_steps1: BigInt,
+    rep_offset: BigInt,
+}
+
+// Look for tandem-repeating blocks of shift rule applications in `meta_log` whose num_apps
+// (given in the parallel `num_apps_log`) grow arithmetically across repetitions, and turn
+// each one into a MetaRule. Blocks that include a raw base step (the `shift_rules.len()`
+// sentinel) are skipped: there is no DiffRule to fit an arithmetic progression to.
+fn find_meta_rules(
+    meta_log: &[MetaEvent],
+    num_apps_log: &[BigInt],
+    shift_rules: &[DiffRule],
+) -> Vec<MetaRule> {
+    let mut rules = Vec::new();
+    let mut pos = 0;
+    for block in find_rep_blocks(meta_log).iter() {
+        let period = block.block.len();
+        if block.rep >= 2 && period > 0 {
+            let rep0 = &num_apps_log[pos..pos + period];
+            let rep1 = &num_apps_log[pos + period..pos + 2 * period];
+            let num_regs = shift_rules.first().map_or(0, |r| r.delta.data.len());
+            let mut delta0 = vec![BigInt::from(0); num_regs];
+            let mut delta1 = vec![BigInt::from(0); num_regs];
+            let mut base_steps0 = BigInt::from(0);
+            let mut base_steps1 = BigInt::from(0);
+            let mut ok = num_regs > 0;
+            for (j, ev) in block.block.iter().enumerate() {
+                if !ok || ev.rule_id >= shift_rules.len() {
+                    ok = false;
+                    break;
+                }
+                let rule = &shift_rules[ev.rule_id];
+                let step = rep1[j].clone() - rep0[j].clone();
+                for (i, del) in rule.delta.data.iter().enumerate() {
+                    delta0[i] += *del * rep0[j].clone();
+                    delta1[i] += *del * step.clone();
+                }
+                base_steps0 += BigInt::from(rule.num_steps) * rep0[j].clone();
+                base_steps1 += BigInt::from(rule.num_steps) * step.clone();
+            }
+            if ok {
+                rules.push(MetaRule {
+                    rule_ids: block.block.iter().map(|e| e.rule_id).collect(),
+                    delta0,
+                    delta1,
+                    base_steps0,
+                    base_steps1,
+                    // `rep0`/`rep1` fit the progression at r=0 and r=1, i.e. the first
+                    // `block.rep` repetitions already observed in the log (and already
+                    // reflected in the live state) -- the next one to apply is r=block.rep.
+                    rep_offset: BigInt::from(block.rep),
+                });
+            }
+        }
+        pos += period * block.rep;
+    }
+    rules
+}
+
+// Sum of repetition indices r = rep_offset .. rep_offset+k-1, i.e.
+// k*rep_offset + k*(k-1)/2 -- the multiplier on `delta1`/`base_steps1` below.
+fn rep_index_sum(rule: &MetaRule, k: &BigInt) -> BigInt {
+    let triangular = (k.clone() * (k.clone() - BigInt::from(1))) / BigInt::from(2);
+    k.clone() * rule.rep_offset.clone() + triangular
+}
+
+// State after applying `k` additional whole repetitions of `rule`, continuing the fitted
+// progression from the `rule.rep_offset` repetitions already reflected in `state`, i.e.
+// state + sum_{r=rep_offset}^{rep_offset+k-1} (delta0 + r*delta1).
+fn apply_meta_k(rule: &MetaRule, state: &State, k: &BigInt) -> State {
+    let r_sum = rep_index_sum(rule, k);
+    let data = state
+        .data
+        .iter()
+        .zip(rule.delta0.iter())
+        .zip(rule.delta1.iter())
+        .map(|((val, d0), d1)| val.clone() + k.clone() * d0.clone() + r_sum.clone() * d1.clone())
+        .collect();
+    State { data }
+}
+
+fn meta_k_valid(rule: &MetaRule, state: &State, k: &BigInt) -> bool {
+    apply_meta_k(rule, state, k).data.iter().all(|v| *v >= 0)
+}
+
+// True if `rule` stays valid for every k, not just some largest finite one: each register's
+// value after k repetitions is `val + k*d0 + (k choose 2)*d1`, a quadratic in k whose sign
+// as k -> infinity is determined by d1 (or, if d1 is 0, by d0). A register only threatens to
+// go negative eventually if d1 < 0, or d1 == 0 and d0 < 0; if no register does, the block
+// repeats forever and the program provably never halts.
+fn meta_rule_diverges(rule: &MetaRule) -> bool {
+    rule.delta0
+        .iter()
+        .zip(rule.delta1.iter())
+        .all(|(d0, d1)| *d1 > 0 || (*d1 == 0 && *d0 >= 0))
+}
+
+// Total additional sim_steps and base_steps that applying `rule` `k` whole repetitions
+// corresponds to, in closed form (mirrors `apply_meta_k`'s state formula).
+fn meta_k_steps(rule: &MetaRule, k: &BigInt) -> (Int, BigInt) {
+    let period = BigInt::from(rule.rule_ids.len());
+    let k_steps = (period * k.clone())
+        .to_i64()
+        .expect("meta sim_steps jump should fit in i64");
+    let r_sum = rep_index_sum(rule, k);
+    let base_steps = k.clone() * rule.base_steps0.clone() + r_sum * rule.base_steps1.clone();
+    (k_steps, base_steps)
+}
+
+// Largest k for which `meta_k_valid` holds, found by exponential then binary search.
+// Assumes validity is monotonic in k, which holds whenever the registers the rule depends
+// on are strictly decreasing across repetitions (the common case for a shift rule nested
+// in an outer loop).
+fn max_valid_meta_k(rule: &MetaRule, state: &State) -> BigInt {
+    if !meta_k_valid(rule, state, &BigInt::from(1)) {
+        return BigInt::from(0);
+    }
+    let mut good = BigInt::from(1);
+    let mut bad = BigInt::from(2);
+    while meta_k_valid(rule, state, &bad) {
+        good = bad.clone();
+        bad *= 2;
+    }
+    while &bad - &good > BigInt::from(1) {
+        let mid = (&good + &bad) / 2;
+        if meta_k_valid(rule, state, &mid) {
+            good = mid;
+        } else {
+            bad = mid;
+        }
+    }
+    good
+}
+
+// Polynomial rolling-hash fingerprint of a `State`, used by the Brent's-algorithm loop
+// detector below. `FP_MODULUS` is the largest prime below 2^32; each (arbitrary-precision)
+// register value is reduced mod it via `rug`'s `mod_u` before being folded into the u128
+// accumulator, so the whole fingerprint fits in a u64 regardless of how large the underlying
+// BigInt registers grow.
+const FP_MODULUS: u64 = 4_294_967_291;
+const FP_BASE: u64 = 6_364_136_223_846_793_005 % FP_MODULUS;
+
+fn fingerprint(state: &State) -> u64 {
+    let mut h: u128 = 0;
+    let mut pow: u128 = 1;
+    for val in &state.data {
+        let digit = val.mod_u(FP_MODULUS as u32) as u128;
+        h = (h + digit * pow) % FP_MODULUS as u128;
+        pow = (pow * FP_BASE as u128) % FP_MODULUS as u128;
+    }
+    h as u64
 }
 
+// Distance (in sim_steps) the teleporting checkpoint advances by before the detector starts
+// capping its own growth. See `ShiftSim::check_loop`. Exposed so the binaries that expose a
+// `--loop-teleport-cap`-style flag (see bin/shift-sim.rs, bin/shift-sim-all.rs) can default to
+// the same value as the library instead of duplicating the literal.
+pub const DEFAULT_LOOP_TELEPORT_CAP: Int = 1_000_000;
+
 #[derive(Debug)]
-struct ShiftSim {
+pub struct ShiftSim {
     prog: Program,
     shift_rules: Vec<DiffRule>,
 
     status: SimStatus,
-    base_steps: Int,
+    base_steps: BigInt,
     sim_steps: Int,
     num_shift_steps: Int,
+
+    // Higher-level transcript of which rule fired (or the base-step sentinel) and how many
+    // times, used to discover meta acceleration rules. See `find_meta_rules`.
+    meta_log: Vec<MetaEvent>,
+    meta_num_apps: Vec<BigInt>,
+    num_meta_rules: usize,
+    meta_steps: Int,
+
+    // Teleporting-turtle (single-checkpoint) variant of Brent's cycle detection: an
+    // independent, shift-rule-agnostic proof of non-halting via exact configuration
+    // recurrence. `loop_checkpoint` is the `(fingerprint, state)` at the last teleport (taken
+    // at sim_steps `loop_next_teleport - loop_teleport_dist`); `loop_teleport_dist` is the
+    // power-of-two (capped) distance until the next teleport. See `check_loop`.
+    loop_checkpoint: Option<(u64, State)>,
+    loop_next_teleport: Int,
+    loop_teleport_dist: Int,
+    loop_teleport_cap: Int,
 }
 
 impl ShiftSim {
+    // Run transcript-based shift rule discovery and build a ShiftSim ready to simulate `prog`
+    // from `state` via the `Simulator` trait.
+    pub fn build(prog: Program, state: State, transcript_steps: Int) -> ShiftSim {
+        let shift_rules = find_shift_rules(&prog, state, transcript_steps);
+        ShiftSim::new(prog, shift_rules)
+    }
+
     fn new(prog: Program, shift_rules: Vec<DiffRule>) -> ShiftSim {
         ShiftSim {
             prog,
             shift_rules,
             status: SimStatus::Running,
-            base_steps: 0,
+            base_steps: BigInt::from(0),
             sim_steps: 0,
             num_shift_steps: 0,
+            meta_log: Vec::new(),
+            meta_num_apps: Vec::new(),
+            num_meta_rules: 0,
+            meta_steps: 0,
+            loop_checkpoint: None,
+            loop_next_teleport: 0,
+            loop_teleport_dist: 1,
+            loop_teleport_cap: DEFAULT_LOOP_TELEPORT_CAP,
         }
     }
 
+    // Override the default cap on how far the loop detector's checkpoint is allowed to
+    // teleport ahead between refreshes. A smaller cap catches short cycles sooner (at the
+    // cost of more frequent fingerprint refreshes); the default is generous enough not to
+    // matter for one-off runs but a batch runner simulating many programs may want to shrink
+    // it to bound per-program work.
+    pub fn with_loop_teleport_cap(mut self, cap: Int) -> ShiftSim {
+        self.loop_teleport_cap = cap;
+        self
+    }
+
+    // Cumulative sim_steps taken so far (shift rule and meta rule applications each count as
+    // 1, same unit as `run`'s `num_steps`). Comparable across calls to `run`, unlike
+    // `steps()` (from `Simulator`), which reports raw Fractran base steps.
+    pub fn sim_steps(&self) -> Int {
+        self.sim_steps
+    }
+
+    // Cumulative raw Fractran base steps taken so far, same count `Simulator::steps` reports
+    // but without narrowing to `Int` -- useful for callers (e.g. batch runners) that want the
+    // exact magnitude for huge step counts rather than just a value comparable across backends.
+    pub fn base_steps(&self) -> BigInt {
+        self.base_steps.clone()
+    }
+
     // Returns true if a step was applied, false if halted.
     fn step(&mut self, mut state: State) -> State {
         if self.status != SimStatus::Running {
@@ -62,16 +308,22 @@ impl ShiftSim {
 
         self.sim_steps += 1;
         // First, try to apply each rule
-        for rule in self.shift_rules.iter() {
+        for (rule_id, rule) in self.shift_rules.iter().enumerate() {
             match rule.apply(&state) {
                 ApplyResult::Infinite => {
                     self.num_shift_steps += 1;
                     self.status = SimStatus::Infinite;
                     return state;
                 }
-                ApplyResult::Some { num_apps: _, result } => {
+                ApplyResult::Some {
+                    num_apps,
+                    result,
+                    base_steps,
+                } => {
                     self.num_shift_steps += 1;
-                    // TODO: Calculate number of base steps.
+                    self.base_steps += base_steps;
+                    self.meta_log.push(MetaEvent { rule_id });
+                    self.meta_num_apps.push(num_apps);
                     return result;
                 }
                 ApplyResult::None => {}
@@ -80,32 +332,160 @@ impl ShiftSim {
 
         // Second fall back to doing a basic rule
         if self.prog.step(&mut state) {
-            // TODO: self.base_steps += 1;
+            self.base_steps += 1;
+            self.meta_log.push(MetaEvent {
+                rule_id: self.shift_rules.len(),
+            });
+            self.meta_num_apps.push(BigInt::from(1));
         } else {
             self.status = SimStatus::Halted;
         }
         state
     }
 
+    // Look for repeating patterns among the shift rule applications recorded so far and,
+    // for each one found, either fast-forward through as many additional whole repetitions
+    // as the current state supports in a single meta-step (advancing `sim_steps` and
+    // `base_steps` by the closed-form total), or, if the repetition can be shown to repeat
+    // forever, record that as a proof of non-halting.
+    //
+    // `meta_log` grows by one event on almost every call (`step` pushes one unless it proves
+    // `Infinite`), so `find_meta_rules` -- which rescans it from scratch -- would otherwise run
+    // in full on every single step, making a long run O(n^2)+ in its own transcript length.
+    // `meta_log`/`meta_num_apps` are trimmed to a trailing `DEFAULT_MAX_WINDOW` (the same cap
+    // `tandem_repeat`'s streaming scan uses) once they grow past twice that, bounding each scan
+    // the same way `run_accelerated`'s history window does.
+    fn try_meta_accelerate(&mut self, state: State) -> State {
+        if self.meta_log.len() > 2 * DEFAULT_MAX_WINDOW {
+            let drop = self.meta_log.len() - DEFAULT_MAX_WINDOW;
+            self.meta_log.drain(..drop);
+            self.meta_num_apps.drain(..drop);
+        }
+
+        let meta_rules = find_meta_rules(&self.meta_log, &self.meta_num_apps, &self.shift_rules);
+        self.num_meta_rules = meta_rules.len();
+
+        let mut state = state;
+        for rule in meta_rules.iter() {
+            if self.status != SimStatus::Running {
+                break;
+            }
+            if meta_rule_diverges(rule) {
+                self.status = SimStatus::Infinite;
+                break;
+            }
+            let k = max_valid_meta_k(rule, &state);
+            if k > BigInt::from(0) {
+                state = apply_meta_k(rule, &state, &k);
+                let (sim_steps_delta, base_steps_delta) = meta_k_steps(rule, &k);
+                self.sim_steps += sim_steps_delta;
+                self.base_steps += base_steps_delta;
+                self.meta_steps += 1;
+            }
+        }
+        state
+    }
+
+    // Independent, fingerprint-based loop detector: proves non-halting whenever the exact
+    // same configuration recurs, even when `find_shift_rules` never found a tandem-repeating
+    // block to fit a DiffRule to (e.g. a cycle too short or irregular for the transcript
+    // scan to have caught). Implemented as a single-checkpoint ("teleporting turtle")
+    // variant of Brent's algorithm: a checkpoint fingerprint is compared against every step
+    // and, once `loop_next_teleport` sim_steps have passed, refreshed and the distance to
+    // the next refresh doubled (capped at `loop_teleport_cap`). A fingerprint match is only
+    // trusted once the full `State.data` is confirmed byte-for-byte equal, to rule out a
+    // hash collision. A deterministic register machine that revisits a configuration repeats
+    // the same transitions forever, so a confirmed match is a proof of non-halting.
+    fn check_loop(&mut self, state: &State) {
+        if self.status != SimStatus::Running {
+            return;
+        }
+        let fp = fingerprint(state);
+        let is_repeat = match &self.loop_checkpoint {
+            Some((checkpoint_fp, checkpoint_state)) => {
+                fp == *checkpoint_fp && state == checkpoint_state
+            }
+            None => false,
+        };
+        if is_repeat {
+            self.status = SimStatus::Infinite;
+            return;
+        }
+
+        let should_teleport = match &self.loop_checkpoint {
+            Some(_) => self.sim_steps >= self.loop_next_teleport,
+            None => true,
+        };
+        if should_teleport {
+            if self.loop_checkpoint.is_some() {
+                self.loop_teleport_dist = (self.loop_teleport_dist * 2).min(self.loop_teleport_cap);
+            }
+            self.loop_checkpoint = Some((fp, state.clone()));
+            self.loop_next_teleport = self.sim_steps + self.loop_teleport_dist;
+        }
+    }
+
+    // One iteration of simulation: take a step, then try to meta-accelerate and check for an
+    // exact-configuration loop. Shared by `run` (bounded by `sim_steps`) and the `Simulator`
+    // trait's `step_until` (bounded by `base_steps` instead, to stay comparable with
+    // `BaseSimulator` -- see `steps()` below).
+    fn advance_one(&mut self, state: State) -> State {
+        let state = self.step(state);
+        let state = self.try_meta_accelerate(state);
+        self.check_loop(&state);
+        state
+    }
+
     pub fn run(&mut self, mut state: State, num_steps: Int) -> State {
         while self.status == SimStatus::Running && self.sim_steps < num_steps {
-            state = self.step(state);
+            state = self.advance_one(state);
         }
         state
     }
 }
 
+impl Simulator for ShiftSim {
+    // Bounded by `steps()` (raw Fractran base_steps), not `sim_steps` like `run` -- `limit` here
+    // is meant to be comparable to `BaseSimulator::step_until`'s, per the `Simulator` trait's
+    // contract (see `golden::check_golden_case`, which drives both backends to the same `limit`
+    // and compares them). A single shift/meta rule application can't be subdivided, so `steps()`
+    // may still overshoot `limit` within the last jump taken; callers that need an exact stopping
+    // point should compare against the actual `steps()` reached rather than assume equality.
+    fn step_until(&mut self, mut state: State, limit: Int) -> State {
+        while self.status == SimStatus::Running && self.steps() < limit {
+            state = self.advance_one(state);
+        }
+        state
+    }
+
+    fn status(&self) -> SimStatus {
+        self.status.clone()
+    }
+
+    // `base_steps` (not `sim_steps`) is what's comparable to the base interpreter's step count --
+    // see the existing `test_base_steps_match_plain_sim`.
+    fn steps(&self) -> Int {
+        self.base_steps.to_i64().expect("base_steps should fit in i64")
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct ShiftSimResult {
     pub sim_status: SimStatus,
-    // Number of base Fractran steps
-    pub base_steps: Int,
+    // Number of base Fractran steps. Matches what a non-accelerated simulation
+    // would report at the same configuration, so the two paths cross-validate.
+    pub base_steps: BigInt,
     // Number of "simulator steps" where applying a shift rule counts as 1 sim_step.
     pub sim_steps: Int,
     // Number of shift rules added
     pub num_shift_rules: usize,
     // Number of times shift rules were used
     pub num_shift_steps: Int,
+    // Number of meta rules discovered (tandem-repeating patterns of shift rule
+    // applications whose num_apps grows arithmetically across repetitions).
+    pub num_meta_rules: usize,
+    // Number of times a meta rule was used to jump over many repetitions at once.
+    pub meta_steps: Int,
 }
 
 // Do accelerated simulation via a two part process:
@@ -127,5 +507,261 @@ pub fn shift_sim(
         sim_steps: sim.sim_steps,
         num_shift_rules: sim.shift_rules.len(),
         num_shift_steps: sim.num_shift_steps,
+        num_meta_rules: sim.num_meta_rules,
+        meta_steps: sim.meta_steps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use infinitable::Infinity;
+
+    use crate::program::{Program, State};
+    use crate::prog;
+    use crate::simulator::BaseSimulator;
+    use crate::state_diff::{StateDiff, StateDiffBound};
+    use crate::{sd, sdb};
+
+    // Size 14 champion: halts in 21 steps.
+    fn make_prog() -> Program {
+        prog![-1,  5,  0;
+               0, -1,  3;
+               0,  0, -1]
+    }
+
+    #[test]
+    fn test_base_steps_match_plain_sim() {
+        let start = State::start(&make_prog());
+
+        // Ground truth from the non-accelerated simulator.
+        let mut plain_state = start.clone();
+        let plain_result = make_prog().run(&mut plain_state, 1_000);
+        assert!(plain_result.halted);
+
+        let result = shift_sim(make_prog(), start, 100, 1_000);
+        assert_eq!(result.sim_status, SimStatus::Halted);
+        assert_eq!(result.base_steps, BigInt::from(plain_result.total_steps));
+    }
+
+    #[test]
+    fn test_find_meta_rules_fits_arithmetic_progression() {
+        let shift_rules = vec![DiffRule {
+            min: sd![0, 0],
+            max: sdb![Infinity, Infinity],
+            delta: sd![-1, 2],
+            num_steps: 1,
+        }];
+        // Same rule fires twice, with num_apps growing from 3 to 5 (+2 per repetition).
+        let meta_log = vec![MetaEvent { rule_id: 0 }, MetaEvent { rule_id: 0 }];
+        let num_apps_log = vec![BigInt::from(3), BigInt::from(5)];
+
+        let meta_rules = find_meta_rules(&meta_log, &num_apps_log, &shift_rules);
+        assert_eq!(meta_rules.len(), 1);
+        assert_eq!(meta_rules[0].rule_ids, vec![0]);
+        assert_eq!(meta_rules[0].delta0, vec![BigInt::from(-3), BigInt::from(6)]);
+        assert_eq!(meta_rules[0].delta1, vec![BigInt::from(-2), BigInt::from(4)]);
+        // Both observed repetitions (r=0, num_apps=3 and r=1, num_apps=5) are already
+        // reflected in the state by the time this rule is found, so the next repetition to
+        // apply is r=2 (num_apps=7), not r=0 again.
+        assert_eq!(meta_rules[0].rep_offset, BigInt::from(2));
+    }
+
+    #[test]
+    fn test_apply_meta_k_continues_progression_from_rep_offset() {
+        // Same fixture as `test_find_meta_rules_fits_arithmetic_progression`: a rule whose
+        // num_apps grew 3, 5, so the third application (r=2) should use num_apps=7, i.e.
+        // delta = rule.delta * 7 = [-7, 14], not rule.delta * 3 (delta0 alone, r=0's value).
+        let rule = MetaRule {
+            rule_ids: vec![0],
+            delta0: vec![BigInt::from(-3), BigInt::from(6)],
+            delta1: vec![BigInt::from(-2), BigInt::from(4)],
+            base_steps0: BigInt::from(3),
+            base_steps1: BigInt::from(2),
+            rep_offset: BigInt::from(2),
+        };
+        let state = State {
+            data: vec![BigInt::from(100), BigInt::from(0)],
+        };
+
+        let next = apply_meta_k(&rule, &state, &BigInt::from(1));
+        assert_eq!(next.data, vec![BigInt::from(93), BigInt::from(14)]);
+
+        let (sim_steps_delta, base_steps_delta) = meta_k_steps(&rule, &BigInt::from(1));
+        assert_eq!(sim_steps_delta, 1);
+        assert_eq!(base_steps_delta, BigInt::from(7));
+    }
+
+    #[test]
+    fn test_find_meta_rules_skips_blocks_with_base_steps() {
+        let shift_rules = vec![DiffRule {
+            min: sd![0],
+            max: sdb![Infinity],
+            delta: sd![-1],
+            num_steps: 1,
+        }];
+        // rule_id 1 is the base-step sentinel (shift_rules.len()), so this block can't be
+        // fit to a single DiffRule and should be skipped.
+        let meta_log = vec![
+            MetaEvent { rule_id: 0 },
+            MetaEvent { rule_id: 1 },
+            MetaEvent { rule_id: 0 },
+            MetaEvent { rule_id: 1 },
+        ];
+        let num_apps_log = vec![
+            BigInt::from(1),
+            BigInt::from(1),
+            BigInt::from(1),
+            BigInt::from(1),
+        ];
+
+        assert!(find_meta_rules(&meta_log, &num_apps_log, &shift_rules).is_empty());
+    }
+
+    #[test]
+    fn test_max_valid_meta_k_linear() {
+        // Each repetition decreases register 0 by a constant 1.
+        let rule = MetaRule {
+            rule_ids: vec![0],
+            delta0: vec![BigInt::from(-1)],
+            delta1: vec![BigInt::from(0)],
+            base_steps0: BigInt::from(1),
+            base_steps1: BigInt::from(0),
+            rep_offset: BigInt::from(0),
+        };
+        let state = State {
+            data: vec![BigInt::from(10)],
+        };
+        assert_eq!(max_valid_meta_k(&rule, &state), BigInt::from(10));
+        assert!(!meta_rule_diverges(&rule));
+    }
+
+    #[test]
+    fn test_max_valid_meta_k_quadratic() {
+        // Each repetition decreases register 0 by one more than the last (0, -1, -2, ...),
+        // so the cumulative decrease is triangular (quadratic in k).
+        let rule = MetaRule {
+            rule_ids: vec![0],
+            delta0: vec![BigInt::from(0)],
+            delta1: vec![BigInt::from(-1)],
+            base_steps0: BigInt::from(1),
+            base_steps1: BigInt::from(0),
+            rep_offset: BigInt::from(0),
+        };
+        let state = State {
+            data: vec![BigInt::from(0)],
+        };
+        assert_eq!(max_valid_meta_k(&rule, &state), BigInt::from(1));
+    }
+
+    #[test]
+    fn test_meta_rule_diverges() {
+        // Strictly growing register: diverges regardless of the constant term.
+        let growing = MetaRule {
+            rule_ids: vec![0],
+            delta0: vec![BigInt::from(-5)],
+            delta1: vec![BigInt::from(1)],
+            base_steps0: BigInt::from(1),
+            base_steps1: BigInt::from(0),
+            rep_offset: BigInt::from(0),
+        };
+        assert!(meta_rule_diverges(&growing));
+
+        // Constant non-negative register: diverges.
+        let flat = MetaRule {
+            rule_ids: vec![0],
+            delta0: vec![BigInt::from(0)],
+            delta1: vec![BigInt::from(0)],
+            base_steps0: BigInt::from(1),
+            base_steps1: BigInt::from(0),
+            rep_offset: BigInt::from(0),
+        };
+        assert!(meta_rule_diverges(&flat));
+
+        // Eventually-shrinking register: does not diverge.
+        let shrinking = MetaRule {
+            rule_ids: vec![0],
+            delta0: vec![BigInt::from(0)],
+            delta1: vec![BigInt::from(-1)],
+            base_steps0: BigInt::from(1),
+            base_steps1: BigInt::from(0),
+            rep_offset: BigInt::from(0),
+        };
+        assert!(!meta_rule_diverges(&shrinking));
+    }
+
+    #[test]
+    fn test_fingerprint_matches_on_equal_states_only() {
+        let a = State { data: vec![BigInt::from(1), BigInt::from(0)] };
+        let b = State { data: vec![BigInt::from(1), BigInt::from(0)] };
+        let c = State { data: vec![BigInt::from(0), BigInt::from(1)] };
+        assert_eq!(fingerprint(&a), fingerprint(&b));
+        assert_ne!(fingerprint(&a), fingerprint(&c));
+    }
+
+    #[test]
+    fn test_meta_acceleration_matches_plain_sim_across_growing_rounds() {
+        // A single shift rule "burst -= 1, round_cap -= 1" (hand-supplied, bypassing
+        // `find_shift_rules` discovery, per `test_loop_detector_proves_non_halting_without_
+        // shift_rules` above) is driven through several "rounds" by bumping `round_cap`
+        // externally before each `run` call -- `round_cap` is always the binding register
+        // (`burst` starts far larger), so each round's shift-rule application consumes
+        // exactly the bump just added, giving a real meta_log with the SAME rule_id firing
+        // with num_apps growing 3, 5 across rounds. This exercises `find_meta_rules`,
+        // `apply_meta_k`/`meta_k_steps` (via `max_valid_meta_k`'s validity probe) and
+        // `try_meta_accelerate` as real code paths, cross-checked at every round against
+        // `BaseSimulator` running the identical program.
+        //
+        // Note: since `round_cap` is the very register each round drains to exactly 0 (by
+        // construction -- it's what bounds that round's num_apps), there is no state in
+        // this minimal 2-register scenario where a *further* whole repetition (k=1) is ever
+        // valid: `max_valid_meta_k` correctly returns 0 here under both the buggy and fixed
+        // `apply_meta_k`. The numeric scenario where the fix actually changes the answer
+        // (continuing the fitted progression from `rep_offset` instead of restarting it at
+        // r=0) is covered directly by `test_apply_meta_k_continues_progression_from_rep_offset`
+        // above, using the exact rep0=3/rep1=5 fixture this test also produces organically.
+        let rule = DiffRule {
+            min: sd![1, 1],
+            max: sdb![Infinity, Infinity],
+            delta: sd![-1, -1],
+            num_steps: 1,
+        };
+
+        let mut shift = ShiftSim::new(prog![-1, -1], vec![rule]);
+        let mut plain = BaseSimulator::new(prog![-1, -1]);
+
+        let mut shift_state = State { data: vec![BigInt::from(1_000), BigInt::from(0)] };
+        let mut plain_state = shift_state.clone();
+
+        for round_cap in [3, 5] {
+            shift_state.data[1] += BigInt::from(round_cap);
+            plain_state.data[1] += BigInt::from(round_cap);
+
+            shift_state = shift.step_until(shift_state, shift.steps() + round_cap);
+            plain_state = plain.step_until(plain_state, plain.steps() + round_cap);
+
+            assert_eq!(shift_state, plain_state);
+            assert_eq!(shift.status(), plain.status());
+        }
+
+        // The pattern (rule 0 firing with num_apps 3, then 5) was genuinely discovered by
+        // the real `find_meta_rules`, but with `round_cap` back at 0 there's no valid
+        // further repetition to fast-forward -- see the note above.
+        assert_eq!(shift.num_meta_rules, 1);
+        assert_eq!(shift.meta_steps, 0);
+    }
+
+    #[test]
+    fn test_loop_detector_proves_non_halting_without_shift_rules() {
+        // Alternates between (1, 0) and (0, 1) forever: instr 0 fires from (1, 0), instr 1
+        // fires from (0, 1). With no shift rules supplied, `find_shift_rules` never ran, so
+        // only the fingerprint-based loop detector can prove this never halts.
+        let prog = prog![-1,  1;
+                           1, -1];
+        let start = State { data: vec![BigInt::from(1), BigInt::from(0)] };
+
+        let mut sim = ShiftSim::new(prog, Vec::new());
+        sim.run(start, 1_000);
+        assert_eq!(sim.status, SimStatus::Infinite);
     }
 }
@@ -0,0 +1,64 @@
+// Common interface both the base Fractran interpreter and the accelerated `ShiftSim` implement,
+// so a single harness (see `golden`) can drive either backend and compare their reported
+// configuration and step counts.
+
+use crate::program::{Int, Program, SimResult, State};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimStatus {
+    Running,
+    Halted,
+    Infinite,
+}
+
+pub trait Simulator {
+    // Advance until cumulative `steps()` reaches `limit` or the simulator leaves `Running`,
+    // returning the resulting state.
+    fn step_until(&mut self, state: State, limit: Int) -> State;
+    fn status(&self) -> SimStatus;
+    // Cumulative steps taken so far, in units comparable across backends -- raw Fractran steps
+    // for both the base interpreter and, via its `base_steps` tally, `ShiftSim`.
+    fn steps(&self) -> Int;
+}
+
+// The plain, non-accelerated interpreter wrapped up as a `Simulator`: one step == one Fractran
+// rule application.
+#[derive(Debug)]
+pub struct BaseSimulator {
+    prog: Program,
+    status: SimStatus,
+    total_steps: Int,
+}
+
+impl BaseSimulator {
+    pub fn new(prog: Program) -> BaseSimulator {
+        BaseSimulator {
+            prog,
+            status: SimStatus::Running,
+            total_steps: 0,
+        }
+    }
+}
+
+impl Simulator for BaseSimulator {
+    fn step_until(&mut self, mut state: State, limit: Int) -> State {
+        if self.status != SimStatus::Running {
+            return state;
+        }
+        let remaining = (limit - self.total_steps).max(0) as usize;
+        let result: SimResult = self.prog.run(&mut state, remaining);
+        self.total_steps += result.total_steps as Int;
+        if result.halted {
+            self.status = SimStatus::Halted;
+        }
+        state
+    }
+
+    fn status(&self) -> SimStatus {
+        self.status.clone()
+    }
+
+    fn steps(&self) -> Int {
+        self.total_steps
+    }
+}
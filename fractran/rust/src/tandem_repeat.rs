@@ -1,11 +1,24 @@
-// Library for identifying and compressing "tandem repeats" or sections of a message that repeat back-to-back.
+// Library for identifying and compressing "tandem repeats" or sections of a message that repeat
+// back-to-back.
+//
+// `find_repeat_info` finds every maximal tandem repeat ("run": a maximal substring with some
+// period that recurs at least MIN_REPEATS times back-to-back) with a Main-Lorentz-style divide
+// and conquer: recurse on the two halves of the data, then find every run whose span crosses the
+// midpoint via Z-function-based longest-common-extension (LCE) queries. There is no cap on the
+// period, unlike the single-window greedy scan this replaces, so long inductive blocks (common in
+// bouncers) are no longer silently missed. The reported runs may nest or partially overlap;
+// `as_rep_blocks` is where that gets resolved into a single non-overlapping sequence.
 
+use std::cell::RefCell;
 use std::cmp;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
 
 use itertools::Itertools;
 
 const MIN_REPEATS: usize = 2;
-const MAX_WINDOW: usize = 100;
 
 pub trait ToStringVec: Sized {
     fn to_string_one(&self) -> String;
@@ -32,19 +45,52 @@ impl<T: PartialEq + Clone + ToStringVec> ToStringVec for RepBlock<T> {
     }
 }
 
-// Find repeated blocks and parse into RepBlock format.
-pub fn find_rep_blocks<T: PartialEq + Clone + ToStringVec>(data: &[T]) -> Vec<RepBlock<T>> {
+// Find repeated blocks and parse into RepBlock format. Tries the Rabin-Karp rolling-hash scan
+// (`find_repeat_info_streaming`, bounded to `DEFAULT_MAX_WINDOW` the same way chunk3-5's
+// streaming callers use it) first: it's near-linear and, since it confirms every hash match with
+// a direct comparison before trusting it, never reports a repeat that isn't real. That covers the
+// common case this is called on a lot -- transcripts dominated by one or two large repeats -- in
+// a single fast, bounded-memory pass. Falls back to the exhaustive Main-Lorentz search whenever
+// the fast scan finds no repeats at all, since its greedy, single left-to-right pass (and its
+// window cap) can miss a repeat that the exhaustive search, which considers every candidate
+// before choosing and has no period cap, would still find.
+pub fn find_rep_blocks<T: PartialEq + Clone + ToStringVec + Hash + 'static>(data: &[T]) -> Vec<RepBlock<T>> {
+    let (fast_blocks, fast_stats) =
+        find_repeat_info_streaming(data.to_vec().into_iter(), DEFAULT_MAX_WINDOW);
+    let fast_blocks = fast_blocks.collect_vec();
+    if fast_stats.borrow().num_blocks > 0 {
+        return fast_blocks;
+    }
+
     let repeats = find_repeat_info(data);
     as_rep_blocks(data, repeats)
 }
 
+// Build the literal/repeat block sequence for `data` from `repeats`, greedily scanning left to
+// right: among all candidates starting at or after the current position, always take the
+// earliest start, breaking ties by largest coverage (`period * count`) and then by smallest
+// (most primitive) period. `repeats` need not be sorted, disjoint, or already reduced -- that
+// disambiguation happens here, fed from the full (possibly overlapping) run set `find_repeat_info`
+// reports.
 pub fn as_rep_blocks<T: PartialEq + Clone + ToStringVec>(
     data: &[T],
-    repeats: Vec<RepeatInfo>,
+    mut repeats: Vec<RepeatInfo>,
 ) -> Vec<RepBlock<T>> {
+    repeats.sort_by(|a, b| {
+        a.start
+            .cmp(&b.start)
+            .then(b.size().cmp(&a.size()))
+            .then(a.period.cmp(&b.period))
+    });
+
     let mut ret = Vec::new();
     let mut n = 0;
     for repeat in repeats.iter() {
+        if repeat.start < n {
+            // Overlaps a repeat already selected (which started no later and covered at least as
+            // much of the message); skip it.
+            continue;
+        }
         if repeat.start > n {
             ret.push(RepBlock {
                 block: data[n..repeat.start].to_vec(),
@@ -55,7 +101,7 @@ pub fn as_rep_blocks<T: PartialEq + Clone + ToStringVec>(
             block: data[repeat.start..repeat.start + repeat.period].to_vec(),
             rep: repeat.count,
         });
-        n = repeat.start + repeat.period * repeat.count;
+        n = repeat.start + repeat.size();
     }
     if n < data.len() {
         ret.push(RepBlock {
@@ -80,42 +126,107 @@ impl RepeatInfo {
     }
 }
 
-pub fn find_repeat_info<T: PartialEq>(data: &[T]) -> Vec<RepeatInfo> {
-    let mut repeats = Vec::new();
-    let mut start = 0;
-    while start < data.len() {
-        if let Some(repeat) = find_repeat_info_prefix(&data[start..]) {
-            repeats.push(RepeatInfo { start, ..repeat });
-            start += repeat.period * repeat.count;
-        } else {
-            start += 1;
+// Find every maximal tandem repeat in `data` (period uncapped). The result may contain runs that
+// nest or partially overlap; see the module comment and `as_rep_blocks`.
+pub fn find_repeat_info<T: PartialEq + Clone>(data: &[T]) -> Vec<RepeatInfo> {
+    let mut runs = Vec::new();
+    find_runs(data, 0, data.len(), &mut runs);
+    runs
+}
+
+// Z-function: z[i] is the length of the longest common prefix of `s` and `s[i..]` (z[0] is
+// unused). Standard O(n) construction.
+fn z_function<T: PartialEq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![0; n];
+    let (mut l, mut r) = (0usize, 0usize);
+    for i in 1..n {
+        let mut k = if i < r { cmp::min(r - i, z[i - l]) } else { 0 };
+        while i + k < n && s[k] == s[i + k] {
+            k += 1;
+        }
+        z[i] = k;
+        if i + k > r {
+            l = i;
+            r = i + k;
         }
     }
-    repeats
+    z
+}
+
+fn z_at(z: &[usize], i: usize) -> usize {
+    z.get(i).copied().unwrap_or(0)
+}
+
+fn reversed<T: Clone>(s: &[T]) -> Vec<T> {
+    s.iter().rev().cloned().collect()
+}
+
+fn concatenated<T: Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    a.iter().chain(b.iter()).cloned().collect()
 }
 
-fn find_repeat_info_prefix<T: PartialEq>(data: &[T]) -> Option<RepeatInfo> {
-    let mut best: Option<RepeatInfo> = None;
-    let mut max_coverage = 0;
-    let p_max = cmp::min(MAX_WINDOW, data.len() / MIN_REPEATS);
-    for period in 1..=p_max {
-        let mut count = 1;
-        let mut offset = period;
-        while offset + period <= data.len() && data[..period] == data[offset..offset + period] {
-            count += 1;
-            offset += period;
+// Recursively find every maximal run within `data[l..r)`: runs entirely within one half are
+// found by recursion; runs whose span crosses `mid` are found directly here, using 4 Z-arrays
+// (forward and reversed, one pair per half) to answer the needed LCE queries in O(r - l).
+fn find_runs<T: PartialEq + Clone>(data: &[T], l: usize, r: usize, out: &mut Vec<RepeatInfo>) {
+    if r - l < 2 {
+        return;
+    }
+    let mid = (l + r) / 2;
+    find_runs(data, l, mid, out);
+    find_runs(data, mid, r, out);
+
+    let left = &data[l..mid];
+    let right = &data[mid..r];
+    let (dim_l, dim_r) = (left.len(), right.len());
+
+    let zf_right = z_function(right);
+    let zr_left = z_function(&reversed(left));
+    // LCP(right, a suffix of left): used to test whether a period crossing `mid` matches fully
+    // across the boundary.
+    let z_cross_fwd = z_function(&concatenated(right, left));
+    // Mirror image of the above, for periods whose unit starts inside the right half.
+    let z_cross_bwd = z_function(&concatenated(&reversed(left), &reversed(right)));
+
+    // Case 1: a full period ends exactly at `mid` (the unit's earlier copy lies in the left
+    // half). `len` ranges over every period that fits within `left`.
+    for len in 1..=dim_l {
+        let offset = dim_r + (dim_l - len);
+        let m = cmp::min(z_at(&z_cross_fwd, offset), dim_r);
+        let k2 = if m == len {
+            len + z_at(&zf_right, len)
+        } else {
+            m
+        };
+        let k1 = z_at(&zr_left, len);
+        if k1 + k2 >= len {
+            push_run(mid - len - k1, len, mid + k2, out);
         }
-        let coverage = count * period;
-        if count >= MIN_REPEATS && coverage > max_coverage {
-            max_coverage = coverage;
-            best = Some(RepeatInfo {
-                start: 0,
-                period,
-                count,
-            });
+    }
+
+    // Case 2: a full period starts exactly at `mid` (the unit's later copy lies in the right
+    // half). `len` ranges over every period that fits within `right`.
+    for len in 1..=dim_r {
+        let offset = dim_l + (dim_r - len);
+        let m = cmp::min(z_at(&z_cross_bwd, offset), dim_l);
+        let k1 = if m == len {
+            len + z_at(&zr_left, len)
+        } else {
+            m
+        };
+        let k2 = z_at(&zf_right, len);
+        if k1 + k2 >= len {
+            push_run(mid - k1, len, mid + len + k2, out);
         }
     }
-    best
+}
+
+fn push_run(start: usize, period: usize, end: usize, out: &mut Vec<RepeatInfo>) {
+    let count = (end - start) / period;
+    if count >= MIN_REPEATS {
+        out.push(RepeatInfo { start, period, count });
+    }
 }
 
 /// Summary statistics about repeats from a Vec<RepeatInfo>
@@ -146,39 +257,430 @@ pub fn rep_stats(rep_info: &Vec<RepeatInfo>, data_size: usize) -> RepBlockStats
     }
 }
 
+// Cap on the repeat period (and how far back a probe looks) that `find_repeat_info_streaming`
+// will consider. Unlike `find_repeat_info`'s uncapped Main-Lorentz search, the streaming scan
+// never materializes more than `2 * max_window` symbols at once, so periods longer than
+// `max_window` are missed -- a deliberate trade of completeness for a bounded memory footprint.
+pub const DEFAULT_MAX_WINDOW: usize = 4096;
+
+// Polynomial rolling-hash base. Collisions are just a quick-reject false positive here --
+// every hash match is re-verified with a real `==` comparison before being trusted.
+const HASH_BASE: u64 = 1_000_000_007;
+
+fn elem_hash<T: Hash>(x: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    x.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Prefix hashes (and base powers) of `pending`: `prefix[i+1] = prefix[i] * HASH_BASE + hash(x)`,
+// so any substring hash is an O(1) `range_hash` query. Extended incrementally one element at a
+// time via `push` as symbols are appended to `pending`; `rebuild` (an O(pending.len()) full
+// recompute) is only needed after a prefix of `pending` is removed, since every later entry's
+// hash depends on absolute position from index 0. `pending` is bounded by `2 * max_window`, so
+// even a full rebuild is cheap and only happens once every `max_window` pushes.
+struct HashIndex {
+    prefix: Vec<u64>,
+    pow: Vec<u64>,
+}
+
+impl HashIndex {
+    fn new() -> HashIndex {
+        HashIndex { prefix: vec![0], pow: vec![1] }
+    }
+
+    // Append the hash of one new trailing element in O(1).
+    fn push<T: Hash>(&mut self, x: &T) {
+        let h = elem_hash(x);
+        self.prefix.push(self.prefix.last().unwrap().wrapping_mul(HASH_BASE).wrapping_add(h));
+        self.pow.push(self.pow.last().unwrap().wrapping_mul(HASH_BASE));
+    }
+
+    fn rebuild<T: Hash>(&mut self, data: &[T]) {
+        self.prefix.clear();
+        self.pow.clear();
+        self.prefix.push(0);
+        self.pow.push(1);
+        for x in data {
+            self.push(x);
+        }
+    }
+
+    // Hash of data[start..start+len).
+    fn range_hash(&self, start: usize, len: usize) -> u64 {
+        self.prefix[start + len].wrapping_sub(self.prefix[start].wrapping_mul(self.pow[len]))
+    }
+}
+
+// A tandem repeat currently being tracked: `unit` is the period's own content (retained once,
+// independent of `pending`, so a repeat that runs for a huge number of reps costs O(period)
+// memory rather than O(period * count)).
+struct Active<T> {
+    unit: Vec<T>,
+    count: usize,
+    matched: usize,
+}
+
+// Streaming counterpart to `find_rep_blocks`: consumes `T`s one at a time and emits `RepBlock`s
+// as soon as they're finalized, without ever retaining more than `2 * max_window` un-emitted
+// symbols (plus one repeat's worth of `unit`, for however long that repeat keeps running).
+// See the module comment on `find_repeat_info_streaming` for the detection strategy.
+struct StreamingRepeats<T, I: Iterator<Item = T>> {
+    inner: I,
+    max_window: usize,
+    // Symbols seen since the last emitted block that aren't yet claimed by `active`.
+    pending: Vec<T>,
+    hash_index: HashIndex,
+    active: Option<Active<T>>,
+    output: VecDeque<RepBlock<T>>,
+    finished: bool,
+    data_size: usize,
+    size_in_reps: usize,
+    size_in_max_block: usize,
+    stats: Rc<RefCell<RepBlockStats>>,
+}
+
+impl<T: PartialEq + Clone + ToStringVec + Hash, I: Iterator<Item = T>> StreamingRepeats<T, I> {
+    fn emit(&mut self, block: RepBlock<T>) {
+        if block.rep > 1 {
+            let size = block.block.len() * block.rep;
+            self.size_in_reps += size;
+            let mut stats = self.stats.borrow_mut();
+            stats.num_blocks += 1;
+            if block.rep > stats.max_rep {
+                stats.max_rep = block.rep;
+                self.size_in_max_block = size;
+            }
+        }
+        {
+            let mut stats = self.stats.borrow_mut();
+            let data_size = self.data_size as f32;
+            stats.frac_in_reps = self.size_in_reps as f32 / data_size;
+            stats.frac_in_max_block = self.size_in_max_block as f32 / data_size;
+        }
+        self.output.push_back(block);
+    }
+
+    // Hashes-then-verify comparison of pending[i..i+len) and pending[j..j+len).
+    fn range_eq(&self, i: usize, j: usize, len: usize) -> bool {
+        self.hash_index.range_hash(i, len) == self.hash_index.range_hash(j, len)
+            && self.pending[i..i + len] == self.pending[j..j + len]
+    }
+
+    // Look for the best-coverage tandem repeat ending exactly at the tail of `pending`.
+    fn probe_tail(&self) -> Option<(usize, usize)> {
+        let len = self.pending.len();
+        let max_period = cmp::min(self.max_window, len / MIN_REPEATS);
+        let mut best: Option<(usize, usize)> = None;
+        let mut best_coverage = 0;
+        for period in 1..=max_period {
+            if !self.range_eq(len - 2 * period, len - period, period) {
+                continue;
+            }
+            let mut count = 2;
+            while (count + 1) * period <= len
+                && self.range_eq(len - (count + 1) * period, len - count * period, period)
+            {
+                count += 1;
+            }
+            let coverage = period * count;
+            if coverage > best_coverage {
+                best_coverage = coverage;
+                best = Some((period, count));
+            }
+        }
+        best
+    }
+
+    fn push(&mut self, x: T) {
+        self.data_size += 1;
+
+        if let Some(active) = self.active.as_mut() {
+            if x == active.unit[active.matched] {
+                active.matched += 1;
+                if active.matched == active.unit.len() {
+                    active.count += 1;
+                    active.matched = 0;
+                }
+                return;
+            }
+            // The repeat is over: emit it, then recover the partial (broken) repetition
+            // attempt -- it's equal to a prefix of `unit` by construction, since it matched
+            // that far before `x` broke it -- as ordinary pending content.
+            let finished = self.active.take().unwrap();
+            let leftover = finished.unit[..finished.matched].to_vec();
+            self.emit(RepBlock { block: finished.unit, rep: finished.count });
+            for item in &leftover {
+                self.hash_index.push(item);
+            }
+            self.pending.extend(leftover);
+        }
+
+        self.hash_index.push(&x);
+        self.pending.push(x);
+
+        if let Some((period, count)) = self.probe_tail() {
+            let repeat_start = self.pending.len() - period * count;
+            let unit = self.pending[repeat_start..repeat_start + period].to_vec();
+            if repeat_start > 0 {
+                let literal: Vec<T> = self.pending.drain(..repeat_start).collect();
+                self.emit(RepBlock { block: literal, rep: 1 });
+            }
+            // Everything still in `pending` (the just-extracted `unit`, if repeat_start == 0,
+            // plus nothing else) is about to be cleared, so there's no need to reconcile the
+            // hash index with the drain above -- just reset it to empty.
+            self.pending.clear();
+            self.hash_index = HashIndex::new();
+            self.active = Some(Active { unit, count, matched: 0 });
+        } else if self.pending.len() > 2 * self.max_window {
+            let drop = self.pending.len() - self.max_window;
+            let literal: Vec<T> = self.pending.drain(..drop).collect();
+            self.hash_index.rebuild(&self.pending);
+            self.emit(RepBlock { block: literal, rep: 1 });
+        }
+    }
+
+    // Flush whatever's left once the underlying iterator is exhausted: a running repeat can't
+    // extend any further, and any literal tail is real, unclassified data.
+    fn flush_at_end(&mut self) {
+        if let Some(active) = self.active.take() {
+            self.emit(RepBlock { block: active.unit, rep: active.count });
+        }
+        if !self.pending.is_empty() {
+            let literal = std::mem::take(&mut self.pending);
+            self.emit(RepBlock { block: literal, rep: 1 });
+        }
+    }
+}
+
+impl<T: PartialEq + Clone + ToStringVec + Hash, I: Iterator<Item = T>> Iterator
+    for StreamingRepeats<T, I>
+{
+    type Item = RepBlock<T>;
+
+    fn next(&mut self) -> Option<RepBlock<T>> {
+        loop {
+            if let Some(block) = self.output.pop_front() {
+                return Some(block);
+            }
+            if self.finished {
+                return None;
+            }
+            match self.inner.next() {
+                Some(x) => self.push(x),
+                None => {
+                    self.flush_at_end();
+                    self.finished = true;
+                }
+            }
+        }
+    }
+}
+
+// Streaming counterpart to `find_rep_blocks`/`rep_stats`: consumes `data` lazily, bounding
+// memory to roughly `2 * max_window` symbols (plus one repeat's `unit`) instead of
+// materializing the whole transcript up front, at the cost of missing periods longer than
+// `max_window`. Emits the same `RepBlock` sequence `find_rep_blocks` would (had it seen a
+// period that long), as soon as each block is finalized.
+//
+// The returned `RepBlockStats` handle accumulates as the iterator is driven; read it only
+// after the iterator has been fully exhausted, the same way `rep_stats` summarizes a
+// complete `Vec<RepeatInfo>`.
+pub fn find_repeat_info_streaming<T, I>(
+    data: I,
+    max_window: usize,
+) -> (Box<dyn Iterator<Item = RepBlock<T>>>, Rc<RefCell<RepBlockStats>>)
+where
+    T: PartialEq + Clone + ToStringVec + Hash + 'static,
+    I: Iterator<Item = T> + 'static,
+{
+    let stats = Rc::new(RefCell::new(RepBlockStats {
+        num_blocks: 0,
+        max_rep: 0,
+        frac_in_reps: 0.0,
+        frac_in_max_block: 0.0,
+    }));
+    let iter = StreamingRepeats {
+        inner: data,
+        max_window,
+        pending: Vec::new(),
+        hash_index: HashIndex::new(),
+        active: None,
+        output: VecDeque::new(),
+        finished: false,
+        data_size: 0,
+        size_in_reps: 0,
+        size_in_max_block: 0,
+        stats: Rc::clone(&stats),
+    };
+    (Box::new(iter), stats)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // Only needed so the tests below can build RepBlocks directly out of plain ints.
+    impl ToStringVec for i32 {
+        fn to_string_one(&self) -> String {
+            self.to_string()
+        }
+
+        fn to_string_vec(xs: &Vec<Self>) -> String {
+            xs.iter().map(|x| x.to_string()).join(",")
+        }
+    }
+
     #[test]
     fn test_simple() {
         let message = vec![13; 6];
-        let result = find_repeat_info(&message);
-        let expected = vec![RepeatInfo {
-            start: 0,
-            period: 1,
-            count: 6,
-        }];
-        assert_eq!(result, expected);
+        let result = find_rep_blocks(&message);
+        assert_eq!(
+            result,
+            vec![RepBlock {
+                block: vec![13],
+                rep: 6,
+            }]
+        );
     }
 
     #[test]
     fn test_offset() {
         let message = vec![1, 2, 3, 4, 3, 4, 3, 4, 1];
+        let result = find_rep_blocks(&message);
+        assert_eq!(
+            result,
+            vec![
+                RepBlock {
+                    block: vec![1, 2],
+                    rep: 1,
+                },
+                RepBlock {
+                    block: vec![3, 4],
+                    rep: 3,
+                },
+                RepBlock {
+                    block: vec![1],
+                    rep: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_period_well_above_old_window_cap() {
+        // The old find_repeat_info_prefix hard-capped periods at MAX_WINDOW = 100; this one is
+        // 137, repeated 4 times, and must still be found whole.
+        let unit: Vec<i32> = (0..137).collect();
+        let mut message = Vec::new();
+        for _ in 0..4 {
+            message.extend(unit.iter().cloned());
+        }
+        let result = find_rep_blocks(&message);
+        assert_eq!(
+            result,
+            vec![RepBlock {
+                block: unit,
+                rep: 4,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_find_repeat_info_reports_runs_crossing_the_recursive_midpoint() {
+        // A period-3 run that straddles whatever midpoint the divide-and-conquer picks, well
+        // clear of both ends of the message.
+        let message = vec![9, 9, 1, 2, 3, 1, 2, 3, 1, 2, 3, 1, 2, 3, 8, 8];
         let result = find_repeat_info(&message);
-        let expected = vec![RepeatInfo {
+        assert!(result.contains(&RepeatInfo {
             start: 2,
-            period: 2,
-            count: 3,
-        }];
-        assert_eq!(result, expected);
-    }
-
-    // #[test]
-    // fn test_complex() {
-    //     let message: Vec<Symbol> =  // TODO
-    //     let result = find_repeats(&message);
-    //     let expected = vec![RepeatInfo { start: 2, period: 2, count: 3 }];
-    //     assert_eq!(result, expected);
-    // }
+            period: 3,
+            count: 4,
+        }));
+    }
+
+    #[test]
+    fn test_as_rep_blocks_disambiguates_overlapping_runs_by_coverage() {
+        // Two candidate runs both starting at 0 (as could come from the raw, unreduced run set
+        // find_repeat_info now reports): a weak period-1 run and a stronger period-3 run that
+        // covers more of the prefix. A third, non-overlapping run later must pass through
+        // untouched.
+        let data: Vec<i32> = (0..8).collect();
+        let repeats = vec![
+            RepeatInfo {
+                start: 0,
+                period: 1,
+                count: 2,
+            },
+            RepeatInfo {
+                start: 0,
+                period: 3,
+                count: 2,
+            },
+            RepeatInfo {
+                start: 6,
+                period: 1,
+                count: 2,
+            },
+        ];
+        let blocks = as_rep_blocks(&data, repeats);
+        assert_eq!(
+            blocks,
+            vec![
+                RepBlock {
+                    block: vec![0, 1, 2],
+                    rep: 2,
+                },
+                RepBlock {
+                    block: vec![6],
+                    rep: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_streaming_matches_offline_for_offset() {
+        let message = vec![1, 2, 3, 4, 3, 4, 3, 4, 1];
+        let (stream, stats) = find_repeat_info_streaming(message.clone().into_iter(), DEFAULT_MAX_WINDOW);
+        let streamed: Vec<RepBlock<i32>> = stream.collect();
+        assert_eq!(streamed, find_rep_blocks(&message));
+        assert_eq!(stats.borrow().num_blocks, 1);
+        assert_eq!(stats.borrow().max_rep, 3);
+    }
+
+    #[test]
+    fn test_streaming_extends_a_repeat_across_many_pushes() {
+        // A single period repeated 50 times: the streaming detector must keep extending one
+        // `Active` repeat rather than re-finalizing every time it finds 2 reps.
+        let message: Vec<i32> = (0..50).flat_map(|_| vec![7, 8]).collect();
+        let (stream, stats) = find_repeat_info_streaming(message.into_iter(), DEFAULT_MAX_WINDOW);
+        let streamed: Vec<RepBlock<i32>> = stream.collect();
+        assert_eq!(
+            streamed,
+            vec![RepBlock {
+                block: vec![7, 8],
+                rep: 50,
+            }]
+        );
+        assert_eq!(stats.borrow().num_blocks, 1);
+        assert_eq!(stats.borrow().max_rep, 50);
+    }
+
+    #[test]
+    fn test_streaming_respects_bounded_window() {
+        // A period of 137 exceeds a max_window of 16, so unlike `find_rep_blocks` (uncapped),
+        // the streaming scan must miss it and report the whole thing as literal.
+        let unit: Vec<i32> = (0..137).collect();
+        let mut message = Vec::new();
+        for _ in 0..4 {
+            message.extend(unit.iter().cloned());
+        }
+        let (stream, stats) = find_repeat_info_streaming(message.clone().into_iter(), 16);
+        let streamed: Vec<RepBlock<i32>> = stream.collect();
+        assert!(streamed.iter().all(|b| b.rep == 1));
+        let recovered: Vec<i32> = streamed.into_iter().flat_map(|b| b.block).collect();
+        assert_eq!(recovered, message);
+        assert_eq!(stats.borrow().num_blocks, 0);
+    }
 }
@@ -1,9 +1,12 @@
 // Evaluate the "transcript" or rule history for a simulation.
 
+use std::cmp;
+use std::collections::HashSet;
+
 use itertools::Itertools;
 
-use crate::program::{Program, State};
-use crate::tandem_repeat::{RepBlock, ToStringVec};
+use crate::program::{BigInt, Program, SimResult, SmallInt, State};
+use crate::tandem_repeat::{find_rep_blocks, RepBlock, ToStringVec, DEFAULT_MAX_WINDOW};
 
 // A transition is a description of which rule applied at each step and
 // why the previous rules did not apply.
@@ -70,8 +73,22 @@ pub fn transcript(prog: &Program, mut state: State, num_steps: usize) -> Vec<Tra
     ret
 }
 
+// Lazy counterpart to `transcript`: yields one `Trans` per step instead of materializing the
+// whole history up front, so callers that only need a bounded look-back (e.g.
+// `tandem_repeat::find_repeat_info_streaming`) can process transcripts of unbounded length.
+pub fn transcript_iter(prog: Program, mut state: State, num_steps: usize) -> Box<dyn Iterator<Item = Trans>> {
+    let mut steps_left = num_steps;
+    Box::new(std::iter::from_fn(move || {
+        if steps_left == 0 {
+            return None;
+        }
+        steps_left -= 1;
+        step(&prog, &mut state)
+    }))
+}
+
 /// Block of transitions stripped of explicit repeat count (only whether it is repeated or not).
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct StrippedBlock {
     pub block: Vec<Trans>,
     pub is_rep: bool,
@@ -102,6 +119,115 @@ pub fn strip_reps(rep_blocks: Vec<RepBlock<Trans>>) -> Vec<StrippedBlock> {
         .collect()
 }
 
+// Net delta of firing every transition in `block` once, summed register by register.
+fn block_delta(prog: &Program, block: &[Trans]) -> Vec<SmallInt> {
+    let mut delta = vec![0; prog.num_registers()];
+    for trans in block {
+        let instr = &prog.instrs[trans.reg_fail.len()];
+        for (d, v) in delta.iter_mut().zip(instr.data.iter()) {
+            *d += v;
+        }
+    }
+    delta
+}
+
+// A block is "uniform" if every register that gated a rule choice within it (i.e. every
+// register that appears in some Trans.reg_fail) strictly decreases over one pass of the
+// block. That guarantees the same rule choices keep firing for further repetitions, since the
+// inequalities that decided them only get more slack as those registers shrink.
+fn is_uniform_block(block: &[Trans], delta: &[SmallInt]) -> bool {
+    let mut gating_regs: HashSet<usize> = HashSet::new();
+    for trans in block {
+        gating_regs.extend(trans.reg_fail.iter().copied());
+    }
+    gating_regs.iter().all(|&reg| delta[reg] < 0)
+}
+
+// Maximum number of additional whole repetitions of `delta` that `state` can sustain before
+// some register with `delta[i] < 0` would go negative, capped so it doesn't overrun
+// `budget_reps` (the number of repetitions left in the step budget).
+fn max_whole_reps(state: &State, delta: &[SmallInt], budget_reps: usize) -> BigInt {
+    let k = state
+        .data
+        .iter()
+        .zip(delta.iter())
+        .filter(|(_, d)| **d < 0)
+        .map(|(val, d)| val.clone() / -*d)
+        .min();
+    match k {
+        None => BigInt::from(0),
+        Some(k) => cmp::min(k, BigInt::from(budget_reps)),
+    }
+}
+
+// Result of `run_accelerated`: the same SimResult a plain `Program::run` would produce, plus
+// how many macro-jumps (repeated-block accelerations) were taken along the way.
+#[derive(Debug, PartialEq)]
+pub struct AcceleratedSimResult {
+    pub sim_result: SimResult,
+    pub macro_jumps: usize,
+}
+
+// Simulate like `Program::run`, but record the running transcript and, whenever the most
+// recently recorded transitions form a tandem-repeating, uniform block, jump ahead by the
+// maximum number of whole repetitions the state can sustain in one shot instead of
+// single-stepping through them. This lets Collatz-like programs that spin through long
+// near-periodic cycles be driven millions of virtual steps cheaply.
+//
+// `history` only accumulates transitions that haven't yet formed a repeat worth jumping on; it's
+// trimmed to a trailing window of `DEFAULT_MAX_WINDOW` transitions (the same cap
+// `tandem_repeat`'s own streaming scan uses) whenever it grows past twice that, so
+// `find_rep_blocks(&history)` stays near-linear in a bounded window on every step rather than
+// rescanning an ever-growing history. That's the same period-length tradeoff
+// `find_repeat_info_streaming` makes: a uniform block whose period exceeds the window is missed.
+pub fn run_accelerated(prog: &Program, mut state: State, step_budget: usize) -> AcceleratedSimResult {
+    let mut total_steps = 0;
+    let mut macro_jumps = 0;
+    let mut history: Vec<Trans> = Vec::new();
+
+    while total_steps < step_budget {
+        let Some(trans) = step(prog, &mut state) else {
+            return AcceleratedSimResult {
+                sim_result: SimResult {
+                    halted: true,
+                    total_steps,
+                },
+                macro_jumps,
+            };
+        };
+        history.push(trans);
+        total_steps += 1;
+
+        if let Some(block) = find_rep_blocks(&history).pop().filter(|b| b.rep > 1) {
+            let delta = block_delta(prog, &block.block);
+            if is_uniform_block(&block.block, &delta) {
+                let budget_reps = (step_budget - total_steps) / block.block.len();
+                let k = max_whole_reps(&state, &delta, budget_reps);
+                if k > 0 {
+                    for (val, d) in state.data.iter_mut().zip(delta.iter()) {
+                        *val += *d * k.clone();
+                    }
+                    let k_reps = k.to_usize().expect("k is bounded by budget_reps, a usize");
+                    total_steps += k_reps * block.block.len();
+                    macro_jumps += 1;
+                }
+            }
+            history.clear();
+        } else if history.len() > 2 * DEFAULT_MAX_WINDOW {
+            let drop = history.len() - DEFAULT_MAX_WINDOW;
+            history.drain(..drop);
+        }
+    }
+
+    AcceleratedSimResult {
+        sim_result: SimResult {
+            halted: false,
+            total_steps: step_budget,
+        },
+        macro_jumps,
+    }
+}
+
 #[macro_export]
 macro_rules! trans {
     ($($x:expr),* $(,)?) => {
@@ -148,4 +274,34 @@ mod tests {
         let expected_rules = [&vec![0][..], &vec![1; 5][..], &vec![2; 15][..]].concat();
         assert_eq!(rules, expected_rules);
     }
+
+    #[test]
+    fn test_run_accelerated_matches_plain_sim() {
+        // Single register, single self-looping instr: drains from 1000 to 0 one at a time,
+        // a textbook uniform repeating block (no gating registers at all).
+        let prog = prog![-1];
+        let start_state = state![1000];
+
+        let mut plain_state = start_state.clone();
+        let plain_result = prog.run(&mut plain_state, 2_000);
+
+        let result = run_accelerated(&prog, start_state, 2_000);
+        assert_eq!(result.sim_result, plain_result);
+        assert!(result.macro_jumps > 0);
+    }
+
+    #[test]
+    fn test_run_accelerated_matches_plain_sim_when_no_repeat() {
+        // Size 8 champion: halts in 5 steps, too short to ever trigger a macro-jump.
+        let prog = prog![-1,  4;
+                           0, -1];
+        let start_state = state![1, 0];
+
+        let mut plain_state = start_state.clone();
+        let plain_result = prog.run(&mut plain_state, 1_000);
+
+        let result = run_accelerated(&prog, start_state, 1_000);
+        assert_eq!(result.sim_result, plain_result);
+        assert_eq!(result.macro_jumps, 0);
+    }
 }
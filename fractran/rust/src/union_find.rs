@@ -0,0 +1,73 @@
+// Disjoint-set (union-find) over indices 0..n, with union by size and path compression.
+
+pub struct UnionFind {
+    // parent[i] == i for a root; otherwise a link towards the root of i's set.
+    parent: Vec<usize>,
+    // Only meaningful for roots: the size of the set rooted there.
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    // Root of the set containing `i`, compressing every visited node directly onto it.
+    pub fn find(&mut self, i: usize) -> usize {
+        if self.parent[i] != i {
+            self.parent[i] = self.find(self.parent[i]);
+        }
+        self.parent[i]
+    }
+
+    // Merge the sets containing `i` and `j`. Returns false if they were already the same set.
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (ri, rj) = (self.find(i), self.find(j));
+        if ri == rj {
+            return false;
+        }
+        // Union by size: the smaller set's root is linked under the larger one's.
+        let (big, small) = if self.size[ri] >= self.size[rj] { (ri, rj) } else { (rj, ri) };
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+        true
+    }
+
+    // Size of the set containing `i`.
+    pub fn set_size(&mut self, i: usize) -> usize {
+        let root = self.find(i);
+        self.size[root]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_singletons() {
+        let mut uf = UnionFind::new(4);
+        for i in 0..4 {
+            assert_eq!(uf.find(i), i);
+            assert_eq!(uf.set_size(i), 1);
+        }
+    }
+
+    #[test]
+    fn test_union_merges_classes() {
+        let mut uf = UnionFind::new(5);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(1, 2));
+        assert!(!uf.union(0, 2)); // Already merged.
+
+        assert_eq!(uf.find(0), uf.find(1));
+        assert_eq!(uf.find(1), uf.find(2));
+        assert_ne!(uf.find(0), uf.find(3));
+        assert_eq!(uf.set_size(0), 3);
+        assert_eq!(uf.set_size(3), 1);
+        assert_eq!(uf.set_size(4), 1);
+    }
+}
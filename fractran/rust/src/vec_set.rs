@@ -4,9 +4,11 @@
 //
 // These could be used as preconditions for rules or building up CTL sets, etc.
 
+use std::cmp;
 use std::str::FromStr;
 
 use crate::program::{Instr, SmallInt};
+use crate::shift_sim::SimStatus;
 
 // Represents a subset of the natural numbers (0, 1, 2, ...)
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -15,6 +17,8 @@ pub enum NatSet {
     Fixed(SmallInt),
     // Min(n) = [n, inf) is a set containing all integers ≥ n
     Min(SmallInt),
+    // Range(lo, hi) = [lo, hi) is a set containing all integers lo ≤ x < hi
+    Range(SmallInt, SmallInt),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -24,6 +28,15 @@ struct SplitAddResult {
 }
 
 impl NatSet {
+    // Range(lo, hi), normalized: Range(n, n+1) collapses to the equivalent Fixed(n).
+    fn range(lo: SmallInt, hi: SmallInt) -> NatSet {
+        if hi - lo == 1 {
+            NatSet::Fixed(lo)
+        } else {
+            NatSet::Range(lo, hi)
+        }
+    }
+
     // Is self a subset of other?
     pub fn is_subset(self, other: NatSet) -> bool {
         match (self, other) {
@@ -35,6 +48,16 @@ impl NatSet {
             (NatSet::Fixed(a), NatSet::Min(b)) => a >= b,
             // [a,inf) subset [b, inf) iff a ≥ b
             (NatSet::Min(a), NatSet::Min(b)) => a >= b,
+            // [a, inf) subset [lo, hi) is impossible: the former is unbounded.
+            (NatSet::Min(_), NatSet::Range(_, _)) => false,
+            // {a} subset [lo, hi) iff lo ≤ a < hi
+            (NatSet::Fixed(a), NatSet::Range(lo, hi)) => lo <= a && a < hi,
+            // [lo, hi) subset {b} iff it contains the single value b
+            (NatSet::Range(lo, hi), NatSet::Fixed(b)) => lo == b && hi == b + 1,
+            // [lo, hi) subset [b, inf) iff lo ≥ b
+            (NatSet::Range(lo, _), NatSet::Min(b)) => lo >= b,
+            // [lo1, hi1) subset [lo2, hi2) iff lo1 ≥ lo2 and hi1 ≤ hi2
+            (NatSet::Range(lo1, hi1), NatSet::Range(lo2, hi2)) => lo1 >= lo2 && hi1 <= hi2,
         }
     }
 
@@ -46,6 +69,8 @@ impl NatSet {
             NatSet::Fixed(n) => NatSet::Fixed(n + v),
             // [n, inf) + v = [n+v, inf)
             NatSet::Min(n) => NatSet::Min(n + v),
+            // [lo, hi) + v = [lo+v, hi+v)
+            NatSet::Range(lo, hi) => NatSet::Range(lo + v, hi + v),
         }
     }
 
@@ -60,10 +85,18 @@ impl NatSet {
             NatSet::Min(n) if n >= thresh => (Some(self), vec![]),
             // n < thresh -> mixed results:
             //      Above: [thresh, inf)
-            //      Below: {n, n+1, ..., thresh-1}
-            NatSet::Min(n) => (
-                Some(NatSet::Min(thresh)),
-                (n..thresh).map(NatSet::Fixed).collect(),
+            //      Below: [n, thresh) as a single disjoint Range, instead of enumerating singletons.
+            NatSet::Min(n) => (Some(NatSet::Min(thresh)), vec![NatSet::range(n, thresh)]),
+            // lo >= thresh -> all of [lo, hi) is above
+            NatSet::Range(lo, _) if lo >= thresh => (Some(self), vec![]),
+            // hi <= thresh -> all of [lo, hi) is below
+            NatSet::Range(_, hi) if hi <= thresh => (None, vec![self]),
+            // lo < thresh < hi -> mixed results:
+            //      Above: [thresh, hi)
+            //      Below: [lo, thresh)
+            NatSet::Range(lo, hi) => (
+                Some(NatSet::range(thresh, hi)),
+                vec![NatSet::range(lo, thresh)],
             ),
         }
     }
@@ -89,6 +122,11 @@ impl FromStr for NatSet {
             // "8+" -> Min(8)
             let n = n_str.parse::<SmallInt>().map_err(|e| e.to_string())?;
             Ok(NatSet::Min(n))
+        } else if let Some((lo_str, hi_str)) = s.split_once('-') {
+            // "3-7" -> Range(3, 7)
+            let lo = lo_str.parse::<SmallInt>().map_err(|e| e.to_string())?;
+            let hi = hi_str.parse::<SmallInt>().map_err(|e| e.to_string())?;
+            Ok(NatSet::range(lo, hi))
         } else {
             // "13" -> Fixed(13)
             let n = s.parse::<SmallInt>().map_err(|e| e.to_string())?;
@@ -108,6 +146,10 @@ struct SplitApplyResult {
 }
 
 impl VecSet {
+    pub fn new(data: Vec<NatSet>) -> VecSet {
+        VecSet(data)
+    }
+
     // Is self a subset of other?
     pub fn is_subset(&self, other: &VecSet) -> bool {
         assert_eq!(self.0.len(), other.0.len());
@@ -127,15 +169,21 @@ impl VecSet {
     //      Success: New VecSet of all valid states after applying `vs`.
     //      Failure: VecSets that union to cover all cases where `vs` cannot apply.
     fn split_apply(&self, instr: &Instr) -> SplitApplyResult {
-        let split_add_res: Vec<SplitAddResult> = self
+        // Per register: (pre-image of values that succeed, values that fail), both in the
+        // *original* (pre-add) value space.
+        let parts: Vec<(Option<NatSet>, Vec<NatSet>)> = self
             .0
             .iter()
             .zip(instr.data.iter())
-            .map(|(x, v)| x.split_add(*v))
+            .map(|(x, v)| x.partition(-v))
             .collect();
-        // Collect the combination of all successfull NatSets.
+        // Collect the combination of all successfull NatSets (post-add).
         // Or if any are None, this will be None.
-        let success: Option<Vec<NatSet>> = split_add_res.iter().map(|r| r.success).collect();
+        let success: Option<Vec<NatSet>> = parts
+            .iter()
+            .zip(instr.data.iter())
+            .map(|((above, _), v)| above.map(|ns| ns.add(*v)))
+            .collect();
         match success {
             None => SplitApplyResult {
                 success: None,
@@ -143,9 +191,16 @@ impl VecSet {
             },
             Some(success) => {
                 let mut failure = Vec::new();
-                for (reg_num, res) in split_add_res.iter().enumerate() {
-                    for nat_set in res.failure.iter() {
-                        failure.push(self.update(reg_num, *nat_set))
+                // Narrow `current` to the pre-image of values known to succeed in registers
+                // already processed, so failure branches for later registers don't re-cover
+                // states already carved out as failures by earlier ones, keeping the cover disjoint.
+                let mut current = self.clone();
+                for (reg_num, (above, below)) in parts.iter().enumerate() {
+                    for nat_set in below.iter() {
+                        failure.push(current.update(reg_num, *nat_set))
+                    }
+                    if let Some(above) = above {
+                        current = current.update(reg_num, *above);
                     }
                 }
                 SplitApplyResult {
@@ -179,6 +234,209 @@ impl VecSet {
             }
         }
     }
+
+    fn get(&self, index: usize) -> NatSet {
+        self.0[index]
+    }
+
+    // Index of the single coordinate that differs between self and other, if there is exactly one.
+    fn single_diff_index(&self, other: &VecSet) -> Option<usize> {
+        let mut diff_idx = None;
+        for (i, (a, b)) in self.0.iter().zip(other.0.iter()).enumerate() {
+            if a != b {
+                if diff_idx.is_some() {
+                    return None;
+                }
+                diff_idx = Some(i);
+            }
+        }
+        diff_idx
+    }
+
+    // Widening for the CTL decider below: if `self` differs from some already-accumulated
+    // VecSet in exactly one Fixed coordinate, generalize that coordinate to a Min starting at
+    // the smaller of the two values. This turns a coordinate that keeps growing across
+    // re-encountered sets (the classic Collatz-style counter) into an unbounded range, so the
+    // worklist below is guaranteed to eventually stop discovering new sets.
+    fn widen_against(&self, accumulated: &[VecSet]) -> VecSet {
+        let mut widened = self.clone();
+        for other in accumulated {
+            if let Some(idx) = widened.single_diff_index(other)
+                && let (NatSet::Fixed(a), NatSet::Fixed(b)) = (widened.get(idx), other.get(idx))
+            {
+                widened = widened.update(idx, NatSet::Min(cmp::min(a, b)));
+            }
+        }
+        widened
+    }
+}
+
+// A union of VecSets: the set of all Fractran configs covered by any of its members. Lets
+// callers build up a region as a collection of cartesian products instead of needing a
+// single VecSet to express it (which NatSet's per-register shape can't always do).
+#[derive(Debug, PartialEq, Clone)]
+pub struct UnionVecSet(Vec<VecSet>);
+
+impl UnionVecSet {
+    pub fn new(sets: Vec<VecSet>) -> UnionVecSet {
+        UnionVecSet(sets)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Union of the successors of every member. None if any member could halt.
+    pub fn successors(&self, instrs: &[Instr]) -> Option<UnionVecSet> {
+        let mut all = Vec::new();
+        for vs in self.0.iter() {
+            all.extend(vs.successors(instrs)?);
+        }
+        Some(UnionVecSet(all))
+    }
+
+    // Subset of self not already covered by some member of `other`.
+    pub fn minus_covered(&self, other: &UnionVecSet) -> UnionVecSet {
+        UnionVecSet(
+            self.0
+                .iter()
+                .filter(|v| !other.0.iter().any(|o| v.is_subset(o)))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    pub fn union(&self, other: UnionVecSet) -> UnionVecSet {
+        let mut sets = self.0.clone();
+        sets.extend(other.0);
+        UnionVecSet(sets)
+    }
+}
+
+// An independently checkable certificate that `instrs` never halts starting from any config
+// covered by `seed`: the full closed set `members` together with, for every member, a
+// witness recording which member subsumes each of its successors. `verify_certificate` is
+// the tiny trusted core that re-checks this without trusting the search that built it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NonHaltingCertificate {
+    pub seed: Vec<VecSet>,
+    pub members: Vec<VecSet>,
+    // witnesses[i][j] is the index into `members` of the member that subsumes the j-th
+    // successor of members[i] (successors listed in the order `VecSet::successors` returns).
+    pub witnesses: Vec<Vec<usize>>,
+}
+
+// Build a certificate from a finished closed set by recomputing each member's successors and
+// recording which member of the closed set subsumes each one.
+fn build_certificate(
+    instrs: &[Instr],
+    seed: Vec<VecSet>,
+    members: Vec<VecSet>,
+) -> NonHaltingCertificate {
+    let witnesses = members
+        .iter()
+        .map(|v| {
+            let succs = v.successors(instrs).expect("closed set member unexpectedly halts");
+            succs
+                .iter()
+                .map(|succ| {
+                    members
+                        .iter()
+                        .position(|m| succ.is_subset(m))
+                        .expect("closed set is not actually closed under successors")
+                })
+                .collect()
+        })
+        .collect();
+    NonHaltingCertificate { seed, members, witnesses }
+}
+
+// Re-validate a NonHaltingCertificate without trusting the search that produced it: for every
+// listed member, recompute its successors and confirm each is covered by its claimed witness,
+// then confirm every seed configuration is covered by some member.
+pub fn verify_certificate(instrs: &[Instr], cert: &NonHaltingCertificate) -> bool {
+    for (member, witness) in cert.members.iter().zip(cert.witnesses.iter()) {
+        let Some(succs) = member.successors(instrs) else {
+            return false;
+        };
+        if succs.len() != witness.len() {
+            return false;
+        }
+        for (succ, &idx) in succs.iter().zip(witness.iter()) {
+            let Some(claimed) = cert.members.get(idx) else {
+                return false;
+            };
+            if !succ.is_subset(claimed) {
+                return false;
+            }
+        }
+    }
+    cert.seed
+        .iter()
+        .all(|s| cert.members.iter().any(|m| s.is_subset(m)))
+}
+
+// Result of trying to prove a program never halts starting from a cover of VecSets.
+#[derive(Debug, PartialEq)]
+pub struct CtlDeciderResult {
+    // Infinite if a closed set was found, Halted if some reachable config can halt,
+    // Running if the step budget ran out before closure was reached.
+    pub status: SimStatus,
+    // Number of distinct VecSets in the (possibly incomplete) closed set.
+    pub closed_set_size: usize,
+    // Present iff status == SimStatus::Infinite: an independently checkable proof that
+    // doesn't require re-running (or trusting) the search below.
+    pub certificate: Option<NonHaltingCertificate>,
+}
+
+// Try to build a closed set `S` of VecSets proving that `instrs` never halts starting from any
+// config covered by `seed`.
+//
+// Maintains a worklist seeded with `seed` and an accumulator `S`. Pops a VecSet, computes its
+// successors (failing immediately if any member of it could halt), and discards any successor
+// already covered (`is_subset`) by a member already in `S`. Otherwise widens the successor
+// against `S` (see `widen_against`) and adds it to both `S` and the worklist. If the worklist
+// empties within `step_budget` pops, `S` is closed under the transition relation and contains
+// `seed`, so every trajectory from `seed` is infinite.
+pub fn decide_non_halting(instrs: &[Instr], seed: Vec<VecSet>, step_budget: usize) -> CtlDeciderResult {
+    let mut closed: Vec<VecSet> = Vec::new();
+    let mut worklist = seed.clone();
+
+    for _ in 0..step_budget {
+        let Some(v) = worklist.pop() else {
+            return CtlDeciderResult {
+                status: SimStatus::Infinite,
+                closed_set_size: closed.len(),
+                certificate: Some(build_certificate(instrs, seed, closed)),
+            };
+        };
+        if closed.iter().any(|s| v.is_subset(s)) {
+            continue;
+        }
+        let succs = match v.successors(instrs) {
+            None => {
+                return CtlDeciderResult {
+                    status: SimStatus::Halted,
+                    closed_set_size: closed.len(),
+                    certificate: None,
+                };
+            }
+            Some(succs) => succs,
+        };
+        closed.push(v);
+        for succ in succs {
+            let succ = succ.widen_against(&closed);
+            if !closed.iter().any(|s| succ.is_subset(s)) {
+                worklist.push(succ);
+            }
+        }
+    }
+
+    CtlDeciderResult {
+        status: SimStatus::Running,
+        closed_set_size: closed.len(),
+        certificate: None,
+    }
 }
 
 // Light syntax for writing VecSets
@@ -231,6 +489,40 @@ mod tests {
         assert!(!v2.is_subset(&v1));
     }
 
+    #[test]
+    fn test_range_is_subset() {
+        let f8 = NatSet::Fixed(8);
+        let m8 = NatSet::Min(8);
+        let r8_13 = NatSet::Range(8, 13);
+        let r10_13 = NatSet::Range(10, 13);
+
+        assert!(f8.is_subset(r8_13));
+        assert!(!NatSet::Fixed(13).is_subset(r8_13));
+        assert!(r8_13.is_subset(r8_13));
+        assert!(r10_13.is_subset(r8_13));
+        assert!(!r8_13.is_subset(r10_13));
+        assert!(r8_13.is_subset(m8));
+        assert!(!m8.is_subset(r8_13));
+        assert!(!r8_13.is_subset(f8));
+        // Single-element range is a subset of the matching Fixed.
+        assert!(NatSet::Range(8, 9).is_subset(f8));
+    }
+
+    #[test]
+    fn test_range_normalizes_to_fixed() {
+        assert_eq!(NatSet::range(8, 9), NatSet::Fixed(8));
+        assert_eq!(NatSet::range(8, 13), NatSet::Range(8, 13));
+    }
+
+    #[test]
+    fn test_range_from_str() {
+        assert_eq!("3-7".parse(), Ok(NatSet::Range(3, 7)));
+        // Single-element range syntax normalizes to Fixed.
+        assert_eq!("3-4".parse(), Ok(NatSet::Fixed(3)));
+        assert_eq!("13".parse(), Ok(NatSet::Fixed(13)));
+        assert_eq!("8+".parse(), Ok(NatSet::Min(8)));
+    }
+
     #[test]
     fn test_split_add() {
         let f13 = NatSet::Fixed(13);
@@ -284,13 +576,7 @@ mod tests {
                 // [13, inf) - 13 -> [0, inf)
                 success: Some(NatSet::Min(0)),
                 // [8, 13) cannot subtract 13
-                failure: vec![
-                    NatSet::Fixed(8),
-                    NatSet::Fixed(9),
-                    NatSet::Fixed(10),
-                    NatSet::Fixed(11),
-                    NatSet::Fixed(12),
-                ],
+                failure: vec![NatSet::Range(8, 13)],
             }
         );
     }
@@ -323,20 +609,18 @@ mod tests {
             v.clone().split_apply(&rule![0, -10, 0]),
             SplitApplyResult {
                 success: Some(vec_set!["13", "0+", "31+"]),
-                failure: vec![vec_set!["13", "8", "31+"], vec_set!["13", "9", "31+"],],
+                failure: vec![vec_set!["13", "8-10", "31+"]],
             }
         );
         assert_eq!(
             v.clone().split_apply(&rule![1, -10, -34]),
             SplitApplyResult {
                 success: Some(vec_set!["14", "0+", "0+"]),
+                // Disjoint: register 1 is narrowed to its success range (10+) before being
+                // combined with register 2's failure, so these two sets share no state.
                 failure: vec![
-                    vec_set!["13", "8", "31+"],
-                    vec_set!["13", "9", "31+"],
-                    // TODO: Remove overlaps here? Switch the 8+ below to 10+?
-                    vec_set!["13", "8+", "31"],
-                    vec_set!["13", "8+", "32"],
-                    vec_set!["13", "8+", "33"],
+                    vec_set!["13", "8-10", "31+"],
+                    vec_set!["13", "10+", "31-34"],
                 ],
             }
         );
@@ -380,4 +664,87 @@ mod tests {
         let d = vec_set!["10+", "0+", "1", "0"];
         assert_eq!(a.successors(&instrs), Some(vec![b, c, d]));
     }
+
+    #[test]
+    fn test_decide_non_halting() {
+        // Same complex Collatz-like non-halting program as test_successors.
+        let p = prog![
+             1, -2, -1,  0;
+            -1, -1,  2,  0;
+            -1,  0,  0,  3;
+             0,  2,  0, -1;
+             1, -1,  0,  0;
+        ];
+
+        let seed = vec![
+            vec_set!["10+", "0+", "0", "0"],
+            vec_set!["0+", "0", "0+", "24+"],
+            vec_set!["0", "46+", "0", "0+"],
+        ];
+        let result = decide_non_halting(&p.instrs, seed, 1_000);
+        assert_eq!(result.status, SimStatus::Infinite);
+        assert!(result.closed_set_size > 0);
+
+        let cert = result.certificate.expect("Infinite result should carry a certificate");
+        assert_eq!(cert.members.len(), result.closed_set_size);
+        assert!(verify_certificate(&p.instrs, &cert));
+    }
+
+    #[test]
+    fn test_decide_non_halting_detects_halt() {
+        // Size 8 champion: halts in 5 steps.
+        let p = prog![-1,  4;
+                        0, -1];
+        let seed = vec![vec_set!["1", "0"]];
+        let result = decide_non_halting(&p.instrs, seed, 1_000);
+        assert_eq!(result.status, SimStatus::Halted);
+        assert_eq!(result.certificate, None);
+    }
+
+    #[test]
+    fn test_union_vec_set_successors_and_minus_covered() {
+        // Same complex Collatz-like non-halting program as test_successors.
+        let p = prog![
+             1, -2, -1,  0;
+            -1, -1,  2,  0;
+            -1,  0,  0,  3;
+             0,  2,  0, -1;
+             1, -1,  0,  0;
+        ];
+
+        let a = vec_set!["0", "46+", "0", "0"];
+        let b = vec_set!["1", "45+", "0", "0"];
+        let union = UnionVecSet::new(vec![a.clone()]);
+        assert_eq!(union.successors(&p.instrs), Some(UnionVecSet::new(vec![b])));
+
+        // Nothing in `a`'s successors is already covered by `a` itself.
+        let succs = union.successors(&p.instrs).unwrap();
+        assert_eq!(succs.minus_covered(&union), succs);
+
+        // But everything is covered by the union of both.
+        let both = union.union(succs.clone());
+        assert!(succs.minus_covered(&both).is_empty());
+    }
+
+    #[test]
+    fn test_verify_certificate_rejects_tampered_witness() {
+        let p = prog![
+             1, -2, -1,  0;
+            -1, -1,  2,  0;
+            -1,  0,  0,  3;
+             0,  2,  0, -1;
+             1, -1,  0,  0;
+        ];
+        let seed = vec![
+            vec_set!["10+", "0+", "0", "0"],
+            vec_set!["0+", "0", "0+", "24+"],
+            vec_set!["0", "46+", "0", "0+"],
+        ];
+        let result = decide_non_halting(&p.instrs, seed, 1_000);
+        let mut cert = result.certificate.unwrap();
+
+        // Point the first witness at the wrong member; the certificate should no longer verify.
+        cert.witnesses[0][0] = (cert.witnesses[0][0] + 1) % cert.members.len();
+        assert!(!verify_certificate(&p.instrs, &cert));
+    }
 }
@@ -1,5 +1,7 @@
 // Compute number of general recursive functions of a given size.
 
+mod recfn;
+
 use clap::Parser;
 use memoize::memoize;
 use rug::{Float, Integer};
@@ -0,0 +1,330 @@
+// Actually materialize and evaluate the general recursive function terms that `count`/
+// `count_many` only tally, turning this into a Busy-Beaver-style search over the function space.
+//
+// `enumerate(size, arity)` walks the exact same size/arity recurrence `count`/`count_many` use, so
+// its item count always matches `count(size, arity)` -- see the sanity check in `main`. `eval`
+// reduces a term with an explicit continuation stack (no native recursion) so that deep `PrimRec`
+// unrollings can't blow the real call stack, and returns `None` once `step_limit` is exceeded,
+// since `Min` can diverge by construction.
+
+use std::collections::HashMap;
+
+use rug::Integer;
+
+type BigInt = Integer;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RecFn {
+    Zero { arity: usize },
+    Succ,
+    Proj { arity: usize, i: usize },
+    Min(Box<RecFn>),
+    PrimRec(Box<RecFn>, Box<RecFn>),
+    Comp(Box<RecFn>, Vec<RecFn>),
+}
+
+// Every term of a given (size, arity), built by the same recurrence `count` sums over.
+pub fn enumerate(size: usize, arity: usize) -> Box<dyn Iterator<Item = RecFn>> {
+    if size == 0 {
+        return Box::new(std::iter::empty());
+    }
+    if size == 1 {
+        // Atoms: Z^k and P^k_i forall 1 <= i <= k, plus S when arity == 1.
+        let mut atoms = vec![RecFn::Zero { arity }];
+        atoms.extend((1..=arity).map(|i| RecFn::Proj { arity, i }));
+        if arity == 1 {
+            atoms.push(RecFn::Succ);
+        }
+        return Box::new(atoms.into_iter());
+    }
+
+    let n = size - 1;
+
+    // M(f)
+    let minimized: Box<dyn Iterator<Item = RecFn>> =
+        Box::new(enumerate(n, arity + 1).map(|f| RecFn::Min(Box::new(f))));
+
+    // R(g,h)
+    let prim_rec: Box<dyn Iterator<Item = RecFn>> = if arity >= 1 {
+        Box::new((1..n).flat_map(move |x| {
+            let y = n - x;
+            enumerate(x, arity - 1).flat_map(move |g| {
+                enumerate(y, arity + 1).map(move |h| RecFn::PrimRec(Box::new(g.clone()), Box::new(h)))
+            })
+        }))
+    } else {
+        Box::new(std::iter::empty())
+    };
+
+    // C(h, g_1, ..., g_m)
+    let comp: Box<dyn Iterator<Item = RecFn>> = Box::new((1..=n).flat_map(move |x| {
+        let y = n - x;
+        (1..=y).flat_map(move |m| {
+            enumerate(x, m).flat_map(move |h| {
+                enumerate_many(y, arity, m).map(move |gs| RecFn::Comp(Box::new(h.clone()), gs))
+            })
+        })
+    }));
+
+    Box::new(minimized.chain(prim_rec).chain(comp))
+}
+
+// Every way to partition `size` into `num_funcs` consecutive sub-function terms, each of `arity`.
+// Mirrors `count_many`'s recurrence.
+fn enumerate_many(size: usize, arity: usize, num_funcs: usize) -> Box<dyn Iterator<Item = Vec<RecFn>>> {
+    if num_funcs > size {
+        return Box::new(std::iter::empty());
+    }
+    if num_funcs == 0 {
+        return if size == 0 {
+            Box::new(std::iter::once(Vec::new()))
+        } else {
+            Box::new(std::iter::empty())
+        };
+    }
+    Box::new((1..=size).flat_map(move |x| {
+        let y = size - x;
+        enumerate(x, arity).flat_map(move |f| {
+            enumerate_many(y, arity, num_funcs - 1).map(move |mut rest| {
+                rest.insert(0, f.clone());
+                rest
+            })
+        })
+    }))
+}
+
+type CacheKey = (RecFn, Vec<BigInt>);
+
+// What to do with the value that results from evaluating the term `eval` just dispatched, so
+// `eval` never has to recurse natively to unwind a `PrimRec`/`Comp`/`Min` chain.
+enum Cont {
+    // The value that's about to bubble up IS `owner`'s final value verbatim; memoize it and keep
+    // bubbling.
+    Forward { owner: CacheKey },
+    // Evaluating Min(f) on `args`; `n` is the trial value that was just checked for zero-ness.
+    Min { owner: CacheKey, f: RecFn, args: Vec<BigInt>, n: BigInt },
+    // Evaluating PrimRec(g,h) on `rest ++ [k]` with k > 0; we just evaluated
+    // PrimRec(g,h)(rest, k - 1) and still need to apply h(rest, k - 1, that result).
+    PrimRecApplyH { owner: CacheKey, h: RecFn, rest: Vec<BigInt>, k_minus_1: BigInt },
+    // Evaluating Comp(h, gs) on `args`; collecting g_i(args) one at a time.
+    CompCollect { owner: CacheKey, h: RecFn, gs: Vec<RecFn>, args: Vec<BigInt>, next: usize, collected: Vec<BigInt> },
+}
+
+// Either more work to dispatch, or the fully-bubbled-up final answer.
+enum Bubbled {
+    Dispatch(CacheKey),
+    Done(BigInt),
+}
+
+// Feed `value` (the result just computed for whatever `eval` had dispatched) into the top of the
+// continuation stack, popping through every `Cont` that `value` completes.
+fn bubble(stack: &mut Vec<Cont>, cache: &mut HashMap<CacheKey, BigInt>, mut value: BigInt) -> Bubbled {
+    loop {
+        match stack.pop() {
+            None => return Bubbled::Done(value),
+            Some(Cont::Forward { owner }) => {
+                cache.insert(owner, value.clone());
+            }
+            Some(Cont::Min { owner, f, args, n }) => {
+                if value == 0 {
+                    cache.insert(owner, n.clone());
+                    value = n;
+                } else {
+                    let n1 = n + 1;
+                    let mut inner = args.clone();
+                    inner.push(n1.clone());
+                    stack.push(Cont::Min { owner, f: f.clone(), args, n: n1 });
+                    return Bubbled::Dispatch((f, inner));
+                }
+            }
+            Some(Cont::PrimRecApplyH { owner, h, rest, k_minus_1 }) => {
+                let mut h_args = rest;
+                h_args.push(k_minus_1);
+                h_args.push(value);
+                stack.push(Cont::Forward { owner });
+                return Bubbled::Dispatch((h, h_args));
+            }
+            Some(Cont::CompCollect { owner, h, gs, args, next, mut collected }) => {
+                collected.push(value);
+                if next < gs.len() {
+                    let next_g = gs[next].clone();
+                    stack.push(Cont::CompCollect { owner, h, gs, args: args.clone(), next: next + 1, collected });
+                    return Bubbled::Dispatch((next_g, args));
+                } else {
+                    stack.push(Cont::Forward { owner });
+                    return Bubbled::Dispatch((h, collected));
+                }
+            }
+        }
+    }
+}
+
+// Reduce `term` applied to `args`, using an explicit continuation stack in place of native
+// recursion (so a deeply-unrolled PrimRec can't overflow the real call stack). Sub-results are
+// memoized by (term, args), the same way `count`'s recurrence is. Returns `None` once more than
+// `step_limit` terms have been dispatched -- the only way `Min` can fail to converge.
+pub fn eval(term: &RecFn, args: &[BigInt], step_limit: usize) -> Option<BigInt> {
+    let mut cache: HashMap<CacheKey, BigInt> = HashMap::new();
+    let mut stack: Vec<Cont> = Vec::new();
+    let mut cur: CacheKey = (term.clone(), args.to_vec());
+    let mut steps: usize = 0;
+
+    loop {
+        let value = if let Some(v) = cache.get(&cur) {
+            v.clone()
+        } else {
+            steps += 1;
+            if steps > step_limit {
+                return None;
+            }
+            match &cur.0 {
+                RecFn::Zero { .. } => BigInt::from(0),
+                RecFn::Succ => cur.1[0].clone() + 1,
+                RecFn::Proj { i, .. } => cur.1[i - 1].clone(),
+                RecFn::Min(f) => {
+                    let mut inner = cur.1.clone();
+                    inner.push(BigInt::from(0));
+                    stack.push(Cont::Min {
+                        owner: cur.clone(),
+                        f: (**f).clone(),
+                        args: cur.1.clone(),
+                        n: BigInt::from(0),
+                    });
+                    cur = ((**f).clone(), inner);
+                    continue;
+                }
+                RecFn::PrimRec(g, h) => {
+                    let k = cur.1.last().expect("PrimRec term needs >= 1 arg").clone();
+                    let rest = cur.1[..cur.1.len() - 1].to_vec();
+                    if k == 0 {
+                        stack.push(Cont::Forward { owner: cur.clone() });
+                        cur = ((**g).clone(), rest);
+                    } else {
+                        let k_minus_1 = k - 1;
+                        let mut prev_args = rest.clone();
+                        prev_args.push(k_minus_1.clone());
+                        stack.push(Cont::PrimRecApplyH {
+                            owner: cur.clone(),
+                            h: (**h).clone(),
+                            rest,
+                            k_minus_1,
+                        });
+                        cur = (cur.0.clone(), prev_args);
+                    }
+                    continue;
+                }
+                RecFn::Comp(h, gs) => {
+                    let first_g = gs[0].clone();
+                    let first_args = cur.1.clone();
+                    stack.push(Cont::CompCollect {
+                        owner: cur.clone(),
+                        h: (**h).clone(),
+                        gs: gs.clone(),
+                        args: cur.1.clone(),
+                        next: 1,
+                        collected: Vec::new(),
+                    });
+                    cur = (first_g, first_args);
+                    continue;
+                }
+            }
+        };
+
+        cache.insert(cur.clone(), value.clone());
+        match bubble(&mut stack, &mut cache, value) {
+            Bubbled::Dispatch(next) => cur = next,
+            Bubbled::Done(final_value) => return Some(final_value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enumerate_count_matches_count() {
+        // Mirrors the sanity check in `main`: `enumerate` must produce exactly `count(size,
+        // arity)` terms, since both walk the same size/arity recurrence.
+        for size in 1..=5 {
+            for arity in 0..=2 {
+                let expected = crate::count(size, arity);
+                let actual = BigInt::from(enumerate(size, arity).count());
+                assert_eq!(actual, expected, "size={size}, arity={arity}");
+            }
+        }
+    }
+
+    // x + y, via PrimRec recursing on y: base case returns x, step case is Succ of the
+    // previous result.
+    fn add_term() -> RecFn {
+        RecFn::PrimRec(
+            Box::new(RecFn::Proj { arity: 1, i: 1 }),
+            Box::new(RecFn::Comp(
+                Box::new(RecFn::Succ),
+                vec![RecFn::Proj { arity: 3, i: 3 }],
+            )),
+        )
+    }
+
+    // x - 1, saturating at 0: PrimRec recursing on the sole arg, base case 0, step case k - 1.
+    fn pred_term() -> RecFn {
+        RecFn::PrimRec(
+            Box::new(RecFn::Zero { arity: 0 }),
+            Box::new(RecFn::Proj { arity: 2, i: 1 }),
+        )
+    }
+
+    // x - y, saturating at 0, via PrimRec recursing on y: base case returns x, step case is
+    // pred of the previous result.
+    fn subtract_term() -> RecFn {
+        RecFn::PrimRec(
+            Box::new(RecFn::Proj { arity: 1, i: 1 }),
+            Box::new(RecFn::Comp(
+                Box::new(pred_term()),
+                vec![RecFn::Proj { arity: 3, i: 3 }],
+            )),
+        )
+    }
+
+    // x * y, via PrimRec recursing on y: base case 0, step case previous result + x.
+    fn mult_term() -> RecFn {
+        RecFn::PrimRec(
+            Box::new(RecFn::Zero { arity: 1 }),
+            Box::new(RecFn::Comp(
+                Box::new(add_term()),
+                vec![RecFn::Proj { arity: 3, i: 3 }, RecFn::Proj { arity: 3, i: 1 }],
+            )),
+        )
+    }
+
+    #[test]
+    fn test_eval_prim_rec_addition() {
+        let sum = eval(&add_term(), &[BigInt::from(3), BigInt::from(4)], 1_000);
+        assert_eq!(sum, Some(BigInt::from(7)));
+    }
+
+    #[test]
+    fn test_eval_prim_rec_multiplication() {
+        let product = eval(&mult_term(), &[BigInt::from(3), BigInt::from(4)], 1_000);
+        assert_eq!(product, Some(BigInt::from(12)));
+    }
+
+    #[test]
+    fn test_eval_min_converges() {
+        // Min(subtract) finds the least n with x - n == 0, i.e. n == x: for x=5 that's 5,
+        // reached only after trying n=0..4 and finding each nonzero.
+        let search = RecFn::Min(Box::new(subtract_term()));
+        let result = eval(&search, &[BigInt::from(5)], 1_000);
+        assert_eq!(result, Some(BigInt::from(5)));
+    }
+
+    #[test]
+    fn test_eval_min_hits_step_limit() {
+        // Min(Succ) searches for the least n with n + 1 == 0, which never happens over the
+        // naturals -- with a small step_limit this must give up and return None rather than
+        // loop forever.
+        let diverging = RecFn::Min(Box::new(RecFn::Succ));
+        assert_eq!(eval(&diverging, &[], 50), None);
+    }
+}